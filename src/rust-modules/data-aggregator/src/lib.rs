@@ -27,7 +27,9 @@ use dashmap::DashMap;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, SyncSender};
 use std::sync::Arc;
+use std::thread::{self, JoinHandle};
 use thiserror::Error;
 
 // ============================================
@@ -48,6 +50,9 @@ pub enum AggregatorError {
 
     #[error("计算错误: {0}")]
     ComputationError(String),
+
+    #[error("序列化错误: {0}")]
+    SerializationError(String),
 }
 
 pub type Result<T> = std::result::Result<T, AggregatorError>;
@@ -238,28 +243,6 @@ impl WelfordAccumulator {
         self.last = Some(value);
     }
 
-    /// 移除旧值（用于滑动窗口）
-    pub fn remove(&mut self, value: f64) {
-        if self.count == 0 {
-            return;
-        }
-
-        self.count -= 1;
-        self.sum -= value;
-
-        if self.count == 0 {
-            self.mean = 0.0;
-            self.m2 = 0.0;
-            self.min = f64::INFINITY;
-            self.max = f64::NEG_INFINITY;
-        } else {
-            let delta = value - self.mean;
-            self.mean -= delta / self.count as f64;
-            let delta2 = value - self.mean;
-            self.m2 -= delta * delta2;
-        }
-    }
-
     /// 合并两个累加器
     pub fn merge(&mut self, other: &WelfordAccumulator) {
         if other.count == 0 {
@@ -330,6 +313,69 @@ impl WelfordAccumulator {
     }
 }
 
+/// 基于单调队列的滑动窗口最值跟踪器
+///
+/// `WelfordAccumulator` 只能累加与合并，无法在极值离开窗口后撤回 `min`/`max`
+/// （它只会一直保留旧的极值）。`MonotonicExtremes` 用两个按时间戳排序的
+/// `VecDeque<(i64, f64)>`（一个递增、一个递减）维护窗口内的最小/最大值，
+/// 插入与淘汰均为摊还 O(1)，可与 `WelfordAccumulator` 搭配使用以获得精确的
+/// 滑动窗口极值。
+#[derive(Debug, Clone, Default)]
+pub struct MonotonicExtremes {
+    /// 按值递增排列，队首为当前窗口最小值
+    min_deque: VecDeque<(i64, f64)>,
+    /// 按值递减排列，队首为当前窗口最大值
+    max_deque: VecDeque<(i64, f64)>,
+}
+
+impl MonotonicExtremes {
+    pub fn new() -> Self {
+        Self {
+            min_deque: VecDeque::new(),
+            max_deque: VecDeque::new(),
+        }
+    }
+
+    /// 插入新的 (时间戳, 值)
+    pub fn insert(&mut self, timestamp: i64, value: f64) {
+        while matches!(self.min_deque.back(), Some(&(_, v)) if v >= value) {
+            self.min_deque.pop_back();
+        }
+        self.min_deque.push_back((timestamp, value));
+
+        while matches!(self.max_deque.back(), Some(&(_, v)) if v <= value) {
+            self.max_deque.pop_back();
+        }
+        self.max_deque.push_back((timestamp, value));
+    }
+
+    /// 淘汰时间戳早于 `cutoff` 的条目
+    pub fn evict(&mut self, cutoff: i64) {
+        while matches!(self.min_deque.front(), Some(&(ts, _)) if ts < cutoff) {
+            self.min_deque.pop_front();
+        }
+        while matches!(self.max_deque.front(), Some(&(ts, _)) if ts < cutoff) {
+            self.max_deque.pop_front();
+        }
+    }
+
+    /// 当前窗口最小值
+    pub fn min(&self) -> Option<f64> {
+        self.min_deque.front().map(|&(_, v)| v)
+    }
+
+    /// 当前窗口最大值
+    pub fn max(&self) -> Option<f64> {
+        self.max_deque.front().map(|&(_, v)| v)
+    }
+
+    /// 清空
+    pub fn reset(&mut self) {
+        self.min_deque.clear();
+        self.max_deque.clear();
+    }
+}
+
 // ============================================
 // 时间窗口聚合器
 // ============================================
@@ -344,11 +390,27 @@ pub struct TimeWindowAggregator {
 /// 窗口状态
 struct WindowState {
     accumulator: WelfordAccumulator,
-    values: VecDeque<(i64, f64)>,
+    /// 用于近似百分位数（p50/p90/p95/p99）的 T-Digest
+    digest: TDigest,
     start_time: i64,
     end_time: i64,
 }
 
+/// `WindowState::digest` 默认的质心上限，在内存占用与尾部分位数精度之间取得平衡
+const DEFAULT_DIGEST_MAX_CENTROIDS: usize = 100;
+
+impl WindowState {
+    fn build_result(&self) -> AggregateResult {
+        let mut result = self.accumulator.get_result();
+        result.window_start = self.start_time;
+        result.window_end = self.end_time;
+        for p in [50u8, 90, 95, 99] {
+            result.percentiles.insert(p, self.digest.percentile(p as f64));
+        }
+        result
+    }
+}
+
 impl TimeWindowAggregator {
     /// 创建新的时间窗口聚合器
     pub fn new(config: WindowConfig) -> Self {
@@ -361,6 +423,11 @@ impl TimeWindowAggregator {
 
     /// 添加数据点
     pub fn add_value(&mut self, timestamp: i64, value: f64) {
+        if matches!(self.config.window_type, WindowType::Session) {
+            self.add_session_value(timestamp, value);
+            return;
+        }
+
         let window_start = self.get_window_start(timestamp);
 
         // 初始化当前窗口
@@ -371,19 +438,109 @@ impl TimeWindowAggregator {
         // 获取或创建窗口
         let window = self.windows.entry(window_start).or_insert_with(|| WindowState {
             accumulator: WelfordAccumulator::new(),
-            values: VecDeque::new(),
+            digest: TDigest::new(DEFAULT_DIGEST_MAX_CENTROIDS),
             start_time: window_start,
             end_time: window_start + self.config.window_size_ms,
         });
 
-        // 添加值
+        // 添加值（只保留增量聚合器，不保留原始数据点，避免内存随到达速率增长）
         window.accumulator.add(value);
-        window.values.push_back((timestamp, value));
+        window.digest.add(value, 1.0);
 
         // 清理过期窗口
         self.cleanup_old_windows(timestamp);
     }
 
+    /// 将数据点归入会话窗口
+    ///
+    /// 会话边界由 `session_gap_ms` 决定：若该点与最近会话的间隔未超过阈值则并入该
+    /// 会话（并据此扩展会话的起止时间）；若该点同时落在两个相邻会话的容忍间隔内
+    /// （乱序到达、恰好桥接两个会话的间隔），则把两个会话合并为一个，累加器通过
+    /// `merge` 组合；否则开启一个新会话。
+    fn add_session_value(&mut self, timestamp: i64, value: f64) {
+        let gap = self.config.session_gap_ms.unwrap_or(i64::MAX);
+
+        let before_key = self.windows.range(..=timestamp).next_back().map(|(&k, _)| k);
+        let after_key = self.windows.range(timestamp + 1..).next().map(|(&k, _)| k);
+
+        let extends_before =
+            before_key.is_some_and(|k| timestamp - self.windows[&k].end_time <= gap);
+        let extends_after =
+            after_key.is_some_and(|k| self.windows[&k].start_time - timestamp <= gap);
+
+        match (extends_before, extends_after) {
+            (true, true) => {
+                // 乱序点桥接了两个相邻会话，合并为一个
+                let before_key = before_key.unwrap();
+                let after_key = after_key.unwrap();
+                let after_state = self.windows.remove(&after_key).unwrap();
+                let before_state = self.windows.get_mut(&before_key).unwrap();
+                before_state.accumulator.add(value);
+                before_state.accumulator.merge(&after_state.accumulator);
+                before_state.digest.add(value, 1.0);
+                before_state.digest.merge(&after_state.digest);
+                before_state.end_time = before_state.end_time.max(after_state.end_time);
+                self.current_window_start = before_key;
+            }
+            (true, false) => {
+                let key = before_key.unwrap();
+                let state = self.windows.get_mut(&key).unwrap();
+                state.accumulator.add(value);
+                state.digest.add(value, 1.0);
+                state.end_time = state.end_time.max(timestamp);
+                self.current_window_start = key;
+            }
+            (false, true) => {
+                // 该点早于已有会话的起始时间但仍在其容忍间隔内，会话起点前移
+                let old_key = after_key.unwrap();
+                let mut state = self.windows.remove(&old_key).unwrap();
+                state.accumulator.add(value);
+                state.digest.add(value, 1.0);
+                state.start_time = timestamp;
+                self.windows.insert(timestamp, state);
+                self.current_window_start = timestamp;
+            }
+            (false, false) => {
+                let mut accumulator = WelfordAccumulator::new();
+                accumulator.add(value);
+                let mut digest = TDigest::new(DEFAULT_DIGEST_MAX_CENTROIDS);
+                digest.add(value, 1.0);
+                self.windows.insert(
+                    timestamp,
+                    WindowState {
+                        accumulator,
+                        digest,
+                        start_time: timestamp,
+                        end_time: timestamp,
+                    },
+                );
+                self.current_window_start = timestamp;
+            }
+        }
+    }
+
+    /// 发射所有因闲置超过 `session_gap_ms` 而过期的会话窗口
+    ///
+    /// 会话窗口不像滚动/滑动窗口那样等到下一个数据点到达才清理，端口传感器这类
+    /// 实时流可能长时间静默，需要主动按墙钟时间 `now` 把已经过期的会话 finalize
+    /// 并发射出去。
+    pub fn flush_expired(&mut self, now: i64) -> Vec<AggregateResult> {
+        let gap = self.config.session_gap_ms.unwrap_or(i64::MAX);
+
+        let expired_keys: Vec<i64> = self
+            .windows
+            .iter()
+            .filter(|(_, w)| now - w.end_time > gap)
+            .map(|(&k, _)| k)
+            .collect();
+
+        expired_keys
+            .into_iter()
+            .filter_map(|k| self.windows.remove(&k))
+            .map(|w| w.build_result())
+            .collect()
+    }
+
     /// 获取窗口起始时间
     fn get_window_start(&self, timestamp: i64) -> i64 {
         match self.config.window_type {
@@ -395,7 +552,7 @@ impl TimeWindowAggregator {
                 (timestamp / slide) * slide
             }
             WindowType::Session => {
-                // 会话窗口需要特殊处理
+                // 会话窗口的归属由 add_session_value 单独处理
                 self.current_window_start
             }
         }
@@ -411,38 +568,17 @@ impl TimeWindowAggregator {
 
     /// 获取当前窗口的聚合结果
     pub fn get_current_aggregate(&self) -> Option<AggregateResult> {
-        self.windows.values().last().map(|w| {
-            let mut result = w.accumulator.get_result();
-            result.window_start = w.start_time;
-            result.window_end = w.end_time;
-            result
-        })
+        self.windows.values().last().map(|w| w.build_result())
     }
 
     /// 获取所有窗口的聚合结果
     pub fn get_all_aggregates(&self) -> Vec<AggregateResult> {
-        self.windows
-            .values()
-            .map(|w| {
-                let mut result = w.accumulator.get_result();
-                result.window_start = w.start_time;
-                result.window_end = w.end_time;
-                result
-            })
-            .collect()
+        self.windows.values().map(|w| w.build_result()).collect()
     }
 
     /// 获取指定时间范围内的聚合结果
     pub fn get_aggregates_in_range(&self, start: i64, end: i64) -> Vec<AggregateResult> {
-        self.windows
-            .range(start..end)
-            .map(|(_, w)| {
-                let mut result = w.accumulator.get_result();
-                result.window_start = w.start_time;
-                result.window_end = w.end_time;
-                result
-            })
-            .collect()
+        self.windows.range(start..end).map(|(_, w)| w.build_result()).collect()
     }
 
     /// 重置聚合器
@@ -527,6 +663,53 @@ impl MultiDimensionAggregator {
         });
     }
 
+    /// 向量化批量添加数据点，返回本批次涉及的不同维度组数
+    ///
+    /// 与 `add_points` 逐点加锁不同：先单次遍历把每个点归入一个分组下标
+    /// （对 `DimensionKey` 做一次哈希分组，而不是对每个点重复查找/创建聚合器），
+    /// 再按组把点的下标分桶，使同一组内的所有值能在一次锁获取下连续写入其
+    /// `TimeWindowAggregator`。锁获取次数从 O(points) 降为 O(groups)，并用 Rayon
+    /// 在组之间并行，而非逐点并行。
+    pub fn add_points_batched(&self, points: &[DataPoint]) -> usize {
+        use rayon::prelude::*;
+
+        if points.is_empty() {
+            return 0;
+        }
+
+        // 第一遍：为每个点计算分组下标，本地哈希表只在这一个线程内使用
+        let mut group_index: HashMap<DimensionKey, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut keys: Vec<DimensionKey> = Vec::new();
+
+        for (i, point) in points.iter().enumerate() {
+            let key = DimensionKey::from_tags(&point.tags);
+            let group = *group_index.entry(key.clone()).or_insert_with(|| {
+                groups.push(Vec::new());
+                keys.push(key);
+                groups.len() - 1
+            });
+            groups[group].push(i);
+        }
+
+        let group_count = groups.len();
+
+        // 第二遍：按组并行，每组只获取一次该维度聚合器的写锁
+        groups.par_iter().zip(keys.par_iter()).for_each(|(indices, key)| {
+            let aggregator = self
+                .aggregators
+                .entry(key.clone())
+                .or_insert_with(|| Arc::new(RwLock::new(TimeWindowAggregator::new(self.config.clone()))));
+
+            let mut guard = aggregator.write();
+            for &i in indices {
+                guard.add_value(points[i].timestamp, points[i].value);
+            }
+        });
+
+        group_count
+    }
+
     /// 获取指定维度的聚合结果
     pub fn get_aggregate(&self, key: &DimensionKey) -> Option<AggregateResult> {
         self.aggregators
@@ -558,11 +741,24 @@ impl MultiDimensionAggregator {
 // ============================================
 
 /// 流式聚合器 - 用于实时数据流处理
+///
+/// 采用分桶（pane）预聚合的滑动窗口：窗口被划分为大小为 `pane_size_ms`（等于
+/// `slide_size_ms`）的若干 pane，每个 pane 只保留一个 `WelfordAccumulator`，从不
+/// 存储原始 `(timestamp, value)`，因此内存占用为 O(窗口内 pane 数) 而非 O(到达速率)。
+/// 发射结果时把所有存活 pane 的累加器通过 `WelfordAccumulator::merge` 折叠成一个，
+/// mean/variance/sum/count 是存活 pane 内全部数据的精确值；最早的 pane 只有在完全
+/// 滑出窗口后才被淘汰，因此它早于 `window_start` 的数据点仍计入这些统计量（pane
+/// 粒度的边界）。min/max 由 [`MonotonicExtremes`] 在每个原始数据点上增量维护，按
+/// `window_start` 精确淘汰，不受 pane 粒度影响。
 pub struct StreamAggregator {
     window_size_ms: i64,
     slide_size_ms: i64,
-    buffer: VecDeque<(i64, f64)>,
-    accumulator: WelfordAccumulator,
+    pane_size_ms: i64,
+    /// 按时间升序排列的 (pane 起始时间, 累加器)
+    panes: VecDeque<(i64, WelfordAccumulator)>,
+    extremes: MonotonicExtremes,
+    /// 用于近似百分位数的 T-Digest，随每个样本增量更新
+    digest: TDigest,
     last_emit_time: i64,
 }
 
@@ -572,28 +768,44 @@ impl StreamAggregator {
         Self {
             window_size_ms,
             slide_size_ms,
-            buffer: VecDeque::new(),
-            accumulator: WelfordAccumulator::new(),
+            pane_size_ms: slide_size_ms.max(1),
+            panes: VecDeque::new(),
+            extremes: MonotonicExtremes::new(),
+            digest: TDigest::new(DEFAULT_DIGEST_MAX_CENTROIDS),
             last_emit_time: 0,
         }
     }
 
+    fn pane_start(&self, timestamp: i64) -> i64 {
+        (timestamp / self.pane_size_ms) * self.pane_size_ms
+    }
+
     /// 处理新数据点
     pub fn process(&mut self, timestamp: i64, value: f64) -> Option<AggregateResult> {
-        // 添加新值
-        self.buffer.push_back((timestamp, value));
-        self.accumulator.add(value);
+        let pane_start = self.pane_start(timestamp);
+
+        // 只落到当前 pane 的累加器上，从不保留原始值
+        match self.panes.back_mut() {
+            Some((start, acc)) if *start == pane_start => acc.add(value),
+            _ => {
+                let mut acc = WelfordAccumulator::new();
+                acc.add(value);
+                self.panes.push_back((pane_start, acc));
+            }
+        }
+        self.extremes.insert(timestamp, value);
+        self.digest.add(value, 1.0);
 
-        // 移除过期数据
+        // 淘汰完全滑出窗口的 pane，以及单调队列中过期的极值候选
         let cutoff = timestamp - self.window_size_ms;
-        while let Some(&(ts, val)) = self.buffer.front() {
-            if ts < cutoff {
-                self.buffer.pop_front();
-                self.accumulator.remove(val);
+        while let Some(&(start, _)) = self.panes.front() {
+            if start + self.pane_size_ms <= cutoff {
+                self.panes.pop_front();
             } else {
                 break;
             }
         }
+        self.extremes.evict(cutoff);
 
         // 检查是否需要发射结果
         if self.last_emit_time == 0 {
@@ -602,27 +814,227 @@ impl StreamAggregator {
 
         if timestamp - self.last_emit_time >= self.slide_size_ms {
             self.last_emit_time = timestamp;
-            let mut result = self.accumulator.get_result();
-            result.window_start = cutoff;
-            result.window_end = timestamp;
-            Some(result)
+            Some(self.build_result(cutoff, timestamp))
         } else {
             None
         }
     }
 
+    /// 将当前存活的所有 pane 折叠为一个累加器
+    fn merge_panes(&self) -> WelfordAccumulator {
+        let mut combined = WelfordAccumulator::new();
+        for (_, acc) in &self.panes {
+            combined.merge(acc);
+        }
+        combined
+    }
+
+    fn build_result(&self, window_start: i64, window_end: i64) -> AggregateResult {
+        let mut result = self.merge_panes().get_result();
+        result.window_start = window_start;
+        result.window_end = window_end;
+        if let Some(min) = self.extremes.min() {
+            result.min = min;
+        }
+        if let Some(max) = self.extremes.max() {
+            result.max = max;
+        }
+        for p in [50u8, 90, 95, 99] {
+            result.percentiles.insert(p, self.digest.percentile(p as f64));
+        }
+        result
+    }
+
     /// 强制发射当前结果
     pub fn flush(&mut self) -> AggregateResult {
-        let result = self.accumulator.get_result();
-        self.buffer.clear();
-        self.accumulator = WelfordAccumulator::new();
+        let result = self.build_result(0, 0);
+        self.panes.clear();
+        self.extremes.reset();
+        self.digest = TDigest::new(DEFAULT_DIGEST_MAX_CENTROIDS);
         result
     }
 
-    /// 获取当前缓冲区大小
+    /// 获取当前驻留的 pane 数量（上限约为 `window_size_ms / pane_size_ms`），
+    /// 而非原始数据点数——这正是分桶预聚合带来的内存优势
     pub fn buffer_size(&self) -> usize {
-        self.buffer.len()
+        self.panes.len()
+    }
+
+    /// 当前存活数据的累加器快照（panes 折叠后的结果），用于跨分片/跨节点合并
+    pub fn accumulator_snapshot(&self) -> WelfordAccumulator {
+        self.merge_panes()
+    }
+
+    /// 当前 T-Digest 的快照，用于跨分片/跨节点合并分位数
+    pub fn digest_snapshot(&self) -> TDigest {
+        self.digest.clone()
+    }
+}
+
+// ============================================
+// 分片并行流式聚合（partition-exchange）
+// ============================================
+
+/// 计算 `key` 应路由到的分片下标——默认实现按 `DimensionKey` 的哈希对 `n` 取模。
+pub fn partition(key: &DimensionKey, n: usize) -> usize {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    (hasher.finish() % n as u64) as usize
+}
+
+enum ShardCommand {
+    Point { key: DimensionKey, timestamp: i64, value: f64 },
+    Flush,
+    Shutdown,
+}
+
+/// 某个 key 的一份分片局部结果：携带原始累加器/digest 快照而非折叠后的
+/// `AggregateResult`，这样 [`multiway_collect`] 才能用 `WelfordAccumulator::merge`
+/// 与 `TDigest::merge` 精确地把同一个 key 的多份局部结果合并成一份。
+#[derive(Debug, Clone)]
+pub struct ShardPartial {
+    pub key: DimensionKey,
+    pub accumulator: WelfordAccumulator,
+    pub digest: TDigest,
+    pub window_start: i64,
+    pub window_end: i64,
+}
+
+/// 按 key 分片的并行流式聚合器（借鉴 Databend 的 pipeline exchange 模型）
+///
+/// 每个分片独占一个工作线程和一个有界 channel；`partition(key, n)` 保证同一个
+/// key 的数据总是路由到同一个分片，分片内部按 key 各自维护一个 `StreamAggregator`，
+/// 因此不同线程之间不需要为同一份数据加锁，仍然能得到精确的单 key 结果。channel
+/// 的容量是有限的：当某个分片消费跟不上时，`process` 会阻塞在 `send` 上，使背压
+/// 直接体现为调用方的延迟，而不是在内部无限堆积。
+pub struct PartitionedStreamAggregator {
+    senders: Vec<SyncSender<ShardCommand>>,
+    handles: Vec<JoinHandle<()>>,
+    output_rx: Receiver<ShardPartial>,
+}
+
+impl PartitionedStreamAggregator {
+    /// 创建一个拥有 `shard_count` 个分片的聚合器；每个分片内的 `StreamAggregator`
+    /// 使用相同的 `window_size_ms`/`slide_size_ms`，`channel_capacity` 是每个分片
+    /// 有界 channel 的容量。
+    pub fn new(
+        shard_count: usize,
+        window_size_ms: i64,
+        slide_size_ms: i64,
+        channel_capacity: usize,
+    ) -> Self {
+        let (output_tx, output_rx) = mpsc::channel::<ShardPartial>();
+        let mut senders = Vec::with_capacity(shard_count);
+        let mut handles = Vec::with_capacity(shard_count);
+
+        for _ in 0..shard_count {
+            let (tx, rx) = mpsc::sync_channel::<ShardCommand>(channel_capacity);
+            let output_tx = output_tx.clone();
+
+            let handle = thread::spawn(move || {
+                let mut aggregators: HashMap<DimensionKey, StreamAggregator> = HashMap::new();
+
+                while let Ok(command) = rx.recv() {
+                    match command {
+                        ShardCommand::Point { key, timestamp, value } => {
+                            // 滑动窗口内部状态是累积快照而非增量，因此每个点不在这里
+                            // 单独产出结果——否则多次 emit 被 multiway_collect 合并时会
+                            // 重复计数。结果只在 Flush 时产出一次，保证可安全合并。
+                            let aggregator = aggregators
+                                .entry(key)
+                                .or_insert_with(|| StreamAggregator::new(window_size_ms, slide_size_ms));
+                            aggregator.process(timestamp, value);
+                        }
+                        ShardCommand::Flush => {
+                            for (key, aggregator) in aggregators.iter_mut() {
+                                let accumulator = aggregator.accumulator_snapshot();
+                                let digest = aggregator.digest_snapshot();
+                                aggregator.flush();
+                                let _ = output_tx.send(ShardPartial {
+                                    key: key.clone(),
+                                    accumulator,
+                                    digest,
+                                    window_start: 0,
+                                    window_end: 0,
+                                });
+                            }
+                        }
+                        ShardCommand::Shutdown => break,
+                    }
+                }
+            });
+
+            senders.push(tx);
+            handles.push(handle);
+        }
+
+        Self { senders, handles, output_rx }
+    }
+
+    /// 路由并处理一个数据点。目标分片的 channel 已满时会阻塞，以此体现背压。
+    pub fn process(&self, key: DimensionKey, timestamp: i64, value: f64) {
+        let shard = partition(&key, self.senders.len());
+        let _ = self.senders[shard].send(ShardCommand::Point { key, timestamp, value });
+    }
+
+    /// 要求所有分片立即 flush 当前状态
+    pub fn flush_all(&self) {
+        for sender in &self.senders {
+            let _ = sender.send(ShardCommand::Flush);
+        }
+    }
+
+    /// 非阻塞地收集目前已产生的所有分片局部结果
+    pub fn collect_available(&self) -> Vec<ShardPartial> {
+        self.output_rx.try_iter().collect()
+    }
+
+    /// 关闭所有分片线程，等待其退出
+    pub fn shutdown(mut self) {
+        for sender in &self.senders {
+            let _ = sender.send(ShardCommand::Shutdown);
+        }
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// 多路归并：把属于同一个 key 的多份分片局部结果合并为一份精确结果
+///
+/// 分片路由保证同一个 key 只落在一个分片上，但同一个 key 的局部结果仍可能分多次
+/// flush 产生（每次 `flush_all` 各产出一份，flush 后分片状态被清空），`multiway_collect`
+/// 把它们按 key 分组后用 `WelfordAccumulator::merge`/`TDigest::merge` 合并。
+pub fn multiway_collect(partials: Vec<ShardPartial>) -> HashMap<DimensionKey, AggregateResult> {
+    let mut merged: HashMap<DimensionKey, (WelfordAccumulator, TDigest, i64, i64)> = HashMap::new();
+
+    for partial in partials {
+        merged
+            .entry(partial.key)
+            .and_modify(|(accumulator, digest, window_start, window_end)| {
+                accumulator.merge(&partial.accumulator);
+                digest.merge(&partial.digest);
+                *window_start = (*window_start).min(partial.window_start);
+                *window_end = (*window_end).max(partial.window_end);
+            })
+            .or_insert((partial.accumulator, partial.digest, partial.window_start, partial.window_end));
     }
+
+    merged
+        .into_iter()
+        .map(|(key, (accumulator, digest, window_start, window_end))| {
+            let mut result = accumulator.get_result();
+            result.window_start = window_start;
+            result.window_end = window_end;
+            for p in [50u8, 90, 95, 99] {
+                result.percentiles.insert(p, digest.percentile(p as f64));
+            }
+            (key, result)
+        })
+        .collect()
 }
 
 // ============================================
@@ -630,6 +1042,13 @@ impl StreamAggregator {
 // ============================================
 
 /// T-Digest 算法实现 - 用于近似百分位数计算
+///
+/// 质心压缩采用标准的 k-scale 方法（而非按固定权重阈值均匀分桶）：
+/// `k(q) = (max_centroids / 2π) · asin(2q − 1)`，只有当合并后跨越的分位数区间
+/// `k(q1) - k(q0) <= 1` 时才把相邻质心合并为一个。该缩放函数在 q 接近 0、1
+/// （即分布的尾部）时导数更大，因此尾部质心天然更小、更精细，中段质心更粗略，
+/// 对端设备延迟 SLA 关心的 p95/p99 等尾部分位数精度更高。
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TDigest {
     centroids: Vec<(f64, f64)>, // (mean, weight)
     max_centroids: usize,
@@ -656,9 +1075,14 @@ impl TDigest {
         }
     }
 
-    /// 压缩质心
+    /// k-scale 函数：把累积分位数 q ∈ [0, 1] 映射到质心尺度空间
+    fn k_scale(&self, q: f64) -> f64 {
+        (self.max_centroids as f64 / (2.0 * std::f64::consts::PI)) * (2.0 * q - 1.0).asin()
+    }
+
+    /// 压缩质心（scale-function 变体）
     fn compress(&mut self) {
-        if self.centroids.is_empty() {
+        if self.centroids.is_empty() || self.total_weight <= 0.0 {
             return;
         }
 
@@ -667,14 +1091,18 @@ impl TDigest {
         let mut new_centroids = Vec::new();
         let mut current_mean = self.centroids[0].0;
         let mut current_weight = self.centroids[0].1;
+        let mut q0 = 0.0_f64;
 
         for &(mean, weight) in &self.centroids[1..] {
             let combined_weight = current_weight + weight;
-            if combined_weight <= self.total_weight / self.max_centroids as f64 {
+            let q1 = q0 + combined_weight / self.total_weight;
+
+            if self.k_scale(q1) - self.k_scale(q0) <= 1.0 {
                 current_mean =
                     (current_mean * current_weight + mean * weight) / combined_weight;
                 current_weight = combined_weight;
             } else {
+                q0 += current_weight / self.total_weight;
                 new_centroids.push((current_mean, current_weight));
                 current_mean = mean;
                 current_weight = weight;
@@ -704,11 +1132,25 @@ impl TDigest {
         self.centroids.last().map(|c| c.0).unwrap_or(0.0)
     }
 
-    /// 合并另一个 T-Digest
+    /// 合并另一个 T-Digest 的质心，并重新跑一遍 scale-function 压缩
+    ///
+    /// 用于跨节点分发部分 digest 后在汇聚端合并，得到全局近似分位数。
     pub fn merge(&mut self, other: &TDigest) {
-        for &(mean, weight) in &other.centroids {
-            self.add(mean, weight);
-        }
+        self.centroids.extend_from_slice(&other.centroids);
+        self.total_weight += other.total_weight;
+        self.compress();
+    }
+
+    /// 序列化为字节数组，便于跨节点传输部分 digest
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self)
+            .map_err(|e| AggregatorError::SerializationError(e.to_string()))
+    }
+
+    /// 从字节数组反序列化
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| AggregatorError::SerializationError(e.to_string()))
     }
 }
 
@@ -716,6 +1158,28 @@ impl TDigest {
 // 聚合管道
 // ============================================
 
+/// 窗口分析函数类型（DataFusion 风格的窗口表达式）：按 `GroupBy` 划分的每个分区
+/// 内，按 `timestamp` 排序后为每一行都输出一个值，而不是把分区折叠成单个聚合值。
+#[derive(Debug, Clone, Copy)]
+pub enum WindowFunctionType {
+    /// 分区内从 1 开始的行号
+    RowNumber,
+    /// 并列的值共享名次，名次之后出现间隙（如 1,1,3）
+    Rank,
+    /// 并列的值共享名次，名次之后不留间隙（如 1,1,2）
+    DenseRank,
+    /// 取分区内前 n 行的值，不存在时为 NaN
+    Lag(usize),
+    /// 取分区内后 n 行的值，不存在时为 NaN
+    Lead(usize),
+    /// 分区内第一行的值
+    FirstValue,
+    /// 分区内最后一行的值
+    LastValue,
+    /// 分区内第 n 行（从 1 开始）的值，不存在时为 NaN
+    NthValue(usize),
+}
+
 /// 聚合操作
 #[derive(Debug, Clone)]
 pub enum AggregateOp {
@@ -724,6 +1188,22 @@ pub enum AggregateOp {
     GroupBy { key_fn: fn(&DataPoint) -> DimensionKey },
     Aggregate { aggregation: AggregationType },
     Window { config: WindowConfig },
+    WindowFunction { function: WindowFunctionType },
+}
+
+/// 窗口函数按行输出的一条结果
+#[derive(Debug, Clone)]
+pub struct RowResult {
+    pub point: DataPoint,
+    pub window_value: f64,
+}
+
+/// 管道执行结果：不含窗口函数时是折叠后的聚合结果；一旦管道中出现
+/// `AggregateOp::WindowFunction`，输出就变成按输入行对齐的逐行结果。
+#[derive(Debug, Clone)]
+pub enum PipelineOutput {
+    Aggregates(Vec<AggregateResult>),
+    Rows(Vec<RowResult>),
 }
 
 /// 聚合管道
@@ -763,41 +1243,171 @@ impl AggregationPipeline {
         self
     }
 
+    pub fn window_function(mut self, function: WindowFunctionType) -> Self {
+        self.operations.push(AggregateOp::WindowFunction { function });
+        self
+    }
+
     /// 执行管道
-    pub fn execute(&self, points: Vec<DataPoint>) -> Vec<AggregateResult> {
+    ///
+    /// 只要管道中不含 `WindowFunction`，返回折叠后的 `AggregateResult`（前面有
+    /// `GroupBy` 时，`Aggregate`/`Window` 按每个分区各自产出结果）。一旦出现 `WindowFunction`，`GroupBy` 划分出的每个分区会
+    /// 先按 `timestamp` 排序，再逐行求窗口函数值，最终返回按输入行对齐的结果。
+    pub fn execute(&self, points: Vec<DataPoint>) -> PipelineOutput {
         let mut current_points = points;
+        let mut partitions: Option<Vec<Vec<DataPoint>>> = None;
         let mut results = Vec::new();
+        let mut row_results: Option<Vec<RowResult>> = None;
 
         for op in &self.operations {
             match op {
                 AggregateOp::Filter { predicate } => {
-                    current_points = current_points.into_iter().filter(predicate).collect();
+                    if let Some(parts) = partitions.as_mut() {
+                        for part in parts.iter_mut() {
+                            *part = std::mem::take(part).into_iter().filter(predicate).collect();
+                        }
+                    } else {
+                        current_points = current_points.into_iter().filter(predicate).collect();
+                    }
                 }
                 AggregateOp::Map { transform } => {
-                    current_points = current_points.iter().map(transform).collect();
+                    if let Some(parts) = partitions.as_mut() {
+                        for part in parts.iter_mut() {
+                            *part = part.iter().map(transform).collect();
+                        }
+                    } else {
+                        current_points = current_points.iter().map(transform).collect();
+                    }
                 }
-                AggregateOp::GroupBy { key_fn: _ } => {
-                    // 分组逻辑
+                AggregateOp::GroupBy { key_fn } => {
+                    let source: Vec<DataPoint> = match partitions.take() {
+                        Some(parts) => parts.into_iter().flatten().collect(),
+                        None => current_points.clone(),
+                    };
+
+                    let mut grouped: HashMap<DimensionKey, Vec<DataPoint>> = HashMap::new();
+                    for point in source {
+                        grouped.entry(key_fn(&point)).or_default().push(point);
+                    }
+                    for partition in grouped.values_mut() {
+                        partition.sort_by_key(|p| p.timestamp);
+                    }
+
+                    partitions = Some(grouped.into_values().collect());
                 }
                 AggregateOp::Aggregate { aggregation: _ } => {
-                    // 聚合逻辑
-                    let mut acc = WelfordAccumulator::new();
-                    for point in &current_points {
-                        acc.add(point.value);
-                    }
-                    results.push(acc.get_result());
+                    results = match &partitions {
+                        Some(parts) => parts
+                            .iter()
+                            .map(|part| {
+                                let mut acc = WelfordAccumulator::new();
+                                for point in part {
+                                    acc.add(point.value);
+                                }
+                                acc.get_result()
+                            })
+                            .collect(),
+                        None => {
+                            let mut acc = WelfordAccumulator::new();
+                            for point in &current_points {
+                                acc.add(point.value);
+                            }
+                            vec![acc.get_result()]
+                        }
+                    };
                 }
                 AggregateOp::Window { config } => {
-                    let mut agg = TimeWindowAggregator::new(config.clone());
-                    for point in &current_points {
-                        agg.add_value(point.timestamp, point.value);
+                    let sources: Vec<&Vec<DataPoint>> = match &partitions {
+                        Some(parts) => parts.iter().collect(),
+                        None => vec![&current_points],
+                    };
+                    results = sources
+                        .into_iter()
+                        .flat_map(|part| {
+                            let mut agg = TimeWindowAggregator::new(config.clone());
+                            for point in part {
+                                agg.add_value(point.timestamp, point.value);
+                            }
+                            agg.get_all_aggregates()
+                        })
+                        .collect();
+                }
+                AggregateOp::WindowFunction { function } => {
+                    let parts: Vec<&Vec<DataPoint>> = match &partitions {
+                        Some(parts) => parts.iter().collect(),
+                        None => vec![&current_points],
+                    };
+
+                    let mut rows = Vec::new();
+                    for part in parts {
+                        let values = Self::apply_window_function(function, part);
+                        for (point, window_value) in part.iter().zip(values) {
+                            rows.push(RowResult {
+                                point: point.clone(),
+                                window_value,
+                            });
+                        }
                     }
-                    results = agg.get_all_aggregates();
+                    row_results = Some(rows);
                 }
             }
         }
 
-        results
+        match row_results {
+            Some(rows) => PipelineOutput::Rows(rows),
+            None => PipelineOutput::Aggregates(results),
+        }
+    }
+
+    /// 对单个（按 timestamp 排序的）分区计算窗口函数，每行输出一个值
+    fn apply_window_function(function: &WindowFunctionType, partition: &[DataPoint]) -> Vec<f64> {
+        let n = partition.len();
+
+        match function {
+            WindowFunctionType::RowNumber => (1..=n).map(|i| i as f64).collect(),
+            WindowFunctionType::Rank => {
+                let mut ranks = Vec::with_capacity(n);
+                let mut current_rank = 1usize;
+                for i in 0..n {
+                    if i > 0 && partition[i].value != partition[i - 1].value {
+                        current_rank = i + 1;
+                    }
+                    ranks.push(current_rank as f64);
+                }
+                ranks
+            }
+            WindowFunctionType::DenseRank => {
+                let mut ranks = Vec::with_capacity(n);
+                let mut current_rank = 0usize;
+                let mut last_value: Option<f64> = None;
+                for point in partition {
+                    if last_value != Some(point.value) {
+                        current_rank += 1;
+                        last_value = Some(point.value);
+                    }
+                    ranks.push(current_rank as f64);
+                }
+                ranks
+            }
+            WindowFunctionType::Lag(k) => (0..n)
+                .map(|i| if i >= *k { partition[i - k].value } else { f64::NAN })
+                .collect(),
+            WindowFunctionType::Lead(k) => (0..n)
+                .map(|i| if i + k < n { partition[i + k].value } else { f64::NAN })
+                .collect(),
+            WindowFunctionType::FirstValue => {
+                let first = partition.first().map(|p| p.value).unwrap_or(f64::NAN);
+                vec![first; n]
+            }
+            WindowFunctionType::LastValue => {
+                let last = partition.last().map(|p| p.value).unwrap_or(f64::NAN);
+                vec![last; n]
+            }
+            WindowFunctionType::NthValue(k) => {
+                let nth = partition.get(k.saturating_sub(1)).map(|p| p.value).unwrap_or(f64::NAN);
+                vec![nth; n]
+            }
+        }
     }
 }
 
@@ -845,6 +1455,60 @@ mod tests {
         assert!((result.mean - 2.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_session_window_splits_on_gap() {
+        let config = WindowConfig::session(500);
+        let mut agg = TimeWindowAggregator::new(config);
+
+        agg.add_value(0, 1.0);
+        agg.add_value(100, 2.0);
+        agg.add_value(200, 3.0);
+        // 间隔 > 500ms，应开启新会话
+        agg.add_value(1000, 10.0);
+        agg.add_value(1100, 20.0);
+
+        let results = agg.get_all_aggregates();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].count, 3);
+        assert!((results[0].mean - 2.0).abs() < 1e-10);
+        assert_eq!(results[1].count, 2);
+        assert!((results[1].mean - 15.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_session_window_merges_on_bridging_out_of_order_point() {
+        let config = WindowConfig::session(500);
+        let mut agg = TimeWindowAggregator::new(config);
+
+        agg.add_value(0, 1.0);
+        agg.add_value(1000, 10.0); // 间隔超过 gap，开启第二个会话
+        assert_eq!(agg.get_all_aggregates().len(), 2);
+
+        // 乱序到达的点恰好落在两个会话的容忍间隔内，桥接合并
+        agg.add_value(500, 5.0);
+
+        let results = agg.get_all_aggregates();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].count, 3);
+        assert!((results[0].mean - (1.0 + 10.0 + 5.0) / 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_session_window_flush_expired() {
+        let config = WindowConfig::session(500);
+        let mut agg = TimeWindowAggregator::new(config);
+
+        agg.add_value(0, 1.0);
+        agg.add_value(100, 2.0);
+
+        assert!(agg.flush_expired(200).is_empty());
+
+        let expired = agg.flush_expired(700);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].count, 2);
+        assert!(agg.get_all_aggregates().is_empty());
+    }
+
     #[test]
     fn test_stream_aggregator() {
         let mut agg = StreamAggregator::new(1000, 500);
@@ -859,6 +1523,80 @@ mod tests {
         assert!(agg.buffer_size() > 0);
     }
 
+    #[test]
+    fn test_stream_aggregator_pane_bucketing_bounds_memory() {
+        let mut agg = StreamAggregator::new(1000, 500);
+
+        // 持续推入远多于窗口容量的数据点，pane 数量应稳定在
+        // window_size_ms / slide_size_ms + 1 左右，而不会随到达总量增长
+        let mut last_result = None;
+        for i in 0..10_000 {
+            let timestamp = i * 10;
+            if let Some(result) = agg.process(timestamp, i as f64) {
+                last_result = Some(result);
+            }
+        }
+
+        assert!(agg.buffer_size() <= 3);
+
+        // 均值应落在窗口时间跨度对应的取值范围内（pane 粒度下边界可能有少量偏差，
+        // 但不应与窗口内数据的量级相差过大）
+        let result = last_result.expect("应当发射过至少一次结果");
+        let window_span = (result.window_end - result.window_start) as f64 / 10.0;
+        assert!(result.count as f64 <= window_span + window_span / 2.0);
+        assert!(result.mean > 0.0);
+    }
+
+    #[test]
+    fn test_monotonic_extremes_matches_brute_force() {
+        let values = [5.0, 3.0, 8.0, 1.0, 9.0, 2.0, 7.0, 4.0, 6.0, 0.0];
+        let window_size_ms = 35;
+        let mut tracker = MonotonicExtremes::new();
+        let mut history: Vec<(i64, f64)> = Vec::new();
+
+        for (i, &value) in values.iter().enumerate() {
+            let timestamp = i as i64 * 10;
+            tracker.insert(timestamp, value);
+            history.push((timestamp, value));
+
+            let cutoff = timestamp - window_size_ms;
+            tracker.evict(cutoff);
+            history.retain(|&(ts, _)| ts >= cutoff);
+
+            let expected_min = history.iter().map(|&(_, v)| v).fold(f64::INFINITY, f64::min);
+            let expected_max = history.iter().map(|&(_, v)| v).fold(f64::NEG_INFINITY, f64::max);
+
+            assert_eq!(tracker.min(), Some(expected_min));
+            assert_eq!(tracker.max(), Some(expected_max));
+        }
+    }
+
+    #[test]
+    fn test_stream_aggregator_reports_exact_min_max_after_eviction() {
+        let mut agg = StreamAggregator::new(50, 20);
+
+        // 窗口早期出现全局最小值，应在其离开窗口后被正确地从 min 中剔除
+        let points = [
+            (1000, 100.0),
+            (1010, -50.0), // 窗口内的全局最小值，稍后会过期
+            (1020, 10.0),
+            (1030, 20.0),
+            (1040, 30.0),
+            (1060, 40.0),
+            (1090, 50.0), // 此时 -50.0 所在的 pane 已经滑出窗口
+        ];
+
+        let mut last_result = None;
+        for &(ts, value) in &points {
+            if let Some(result) = agg.process(ts, value) {
+                last_result = Some(result);
+            }
+        }
+
+        let result = last_result.expect("应当发射过至少一次结果");
+        assert!(result.min > -50.0, "min 应已随窗口滑动而更新，而不是停留在已过期的极值");
+    }
+
     #[test]
     fn test_dimension_key() {
         let key1 = DimensionKey::new()
@@ -885,6 +1623,31 @@ mod tests {
 
         let p90 = td.percentile(90.0);
         assert!((p90 - 90.0).abs() < 5.0);
+
+        // p99 尾部分位数精度应优于均匀分桶的旧实现
+        let p99 = td.percentile(99.0);
+        assert!((p99 - 99.0).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_tdigest_serialization_round_trip_and_merge() {
+        let mut left = TDigest::new(100);
+        for i in 1..=50 {
+            left.add(i as f64, 1.0);
+        }
+
+        let bytes = left.to_bytes().expect("序列化不应失败");
+        let restored = TDigest::from_bytes(&bytes).expect("反序列化不应失败");
+        assert!((restored.percentile(50.0) - left.percentile(50.0)).abs() < 1e-9);
+
+        let mut right = TDigest::new(100);
+        for i in 51..=100 {
+            right.add(i as f64, 1.0);
+        }
+
+        let mut combined = restored;
+        combined.merge(&right);
+        assert!((combined.percentile(50.0) - 50.0).abs() < 5.0);
     }
 
     #[test]
@@ -903,4 +1666,132 @@ mod tests {
         let dimensions = agg.get_dimensions();
         assert_eq!(dimensions.len(), 2);
     }
+
+    #[test]
+    fn test_multi_dimension_aggregator_add_points_batched() {
+        let config = WindowConfig::tumbling(1000);
+        let agg = MultiDimensionAggregator::new(config);
+
+        let points = vec![
+            DataPoint::new(100, 1.0).with_tag("device", "agv_001"),
+            DataPoint::new(200, 2.0).with_tag("device", "agv_001"),
+            DataPoint::new(300, 3.0).with_tag("device", "agv_002"),
+            DataPoint::new(400, 4.0).with_tag("device", "agv_002"),
+            DataPoint::new(500, 5.0).with_tag("device", "agv_003"),
+        ];
+
+        let group_count = agg.add_points_batched(&points);
+        assert_eq!(group_count, 3);
+
+        let dimensions = agg.get_dimensions();
+        assert_eq!(dimensions.len(), 3);
+
+        let key_001 = DimensionKey::new().with_dimension("device", "agv_001");
+        let result = agg.get_aggregate(&key_001).expect("应当存在该维度的聚合结果");
+        assert_eq!(result.count, 2);
+        assert!((result.mean - 1.5).abs() < 1e-10);
+    }
+
+    fn device_key(point: &DataPoint) -> DimensionKey {
+        DimensionKey::from_tags(&point.tags)
+    }
+
+    #[test]
+    fn test_pipeline_row_number_per_partition() {
+        let points = vec![
+            DataPoint::new(300, 30.0).with_tag("device", "agv_001"),
+            DataPoint::new(100, 10.0).with_tag("device", "agv_001"),
+            DataPoint::new(200, 20.0).with_tag("device", "agv_001"),
+            DataPoint::new(100, 99.0).with_tag("device", "agv_002"),
+        ];
+
+        let pipeline = AggregationPipeline::new()
+            .group_by(device_key)
+            .window_function(WindowFunctionType::RowNumber);
+
+        let output = pipeline.execute(points.clone());
+        let rows = match output {
+            PipelineOutput::Rows(rows) => rows,
+            PipelineOutput::Aggregates(_) => panic!("含 WindowFunction 的管道应返回逐行结果"),
+        };
+
+        assert_eq!(rows.len(), 4);
+        let agv_001_rows: Vec<&RowResult> = rows
+            .iter()
+            .filter(|r| r.point.tags.get("device").map(String::as_str) == Some("agv_001"))
+            .collect();
+        assert_eq!(agv_001_rows.len(), 3);
+        // 分区内应按 timestamp 排序后从 1 开始编号
+        assert_eq!(agv_001_rows[0].point.timestamp, 100);
+        assert_eq!(agv_001_rows[0].window_value, 1.0);
+        assert_eq!(agv_001_rows[1].point.timestamp, 200);
+        assert_eq!(agv_001_rows[1].window_value, 2.0);
+        assert_eq!(agv_001_rows[2].point.timestamp, 300);
+        assert_eq!(agv_001_rows[2].window_value, 3.0);
+    }
+
+    #[test]
+    fn test_pipeline_lag_and_rank() {
+        let points = vec![
+            DataPoint::new(100, 5.0),
+            DataPoint::new(200, 5.0),
+            DataPoint::new(300, 7.0),
+        ];
+
+        let lag_pipeline = AggregationPipeline::new().window_function(WindowFunctionType::Lag(1));
+        let lag_rows = match lag_pipeline.execute(points.clone()) {
+            PipelineOutput::Rows(rows) => rows,
+            PipelineOutput::Aggregates(_) => panic!("应返回逐行结果"),
+        };
+        assert!(lag_rows[0].window_value.is_nan());
+        assert_eq!(lag_rows[1].window_value, 5.0);
+        assert_eq!(lag_rows[2].window_value, 5.0);
+
+        let rank_pipeline = AggregationPipeline::new().window_function(WindowFunctionType::Rank);
+        let rank_rows = match rank_pipeline.execute(points) {
+            PipelineOutput::Rows(rows) => rows,
+            PipelineOutput::Aggregates(_) => panic!("应返回逐行结果"),
+        };
+        // 并列的值共享名次，名次之后留有间隙：1, 1, 3
+        assert_eq!(rank_rows[0].window_value, 1.0);
+        assert_eq!(rank_rows[1].window_value, 1.0);
+        assert_eq!(rank_rows[2].window_value, 3.0);
+    }
+
+    #[test]
+    fn test_partition_is_stable_for_same_key() {
+        let key_a = DimensionKey::new().with_dimension("device", "a");
+        let key_b = DimensionKey::new().with_dimension("device", "b");
+
+        let shard_count = 4;
+        for _ in 0..10 {
+            assert_eq!(partition(&key_a, shard_count), partition(&key_a, shard_count));
+            assert_eq!(partition(&key_b, shard_count), partition(&key_b, shard_count));
+        }
+    }
+
+    #[test]
+    fn test_partitioned_stream_aggregator_end_to_end() {
+        let aggregator = PartitionedStreamAggregator::new(2, 1_000, 100, 16);
+        let key_a = DimensionKey::new().with_dimension("device", "a");
+        let key_b = DimensionKey::new().with_dimension("device", "b");
+
+        for i in 0..5 {
+            aggregator.process(key_a.clone(), i * 100, 10.0 + i as f64);
+            aggregator.process(key_b.clone(), i * 100, 100.0 + i as f64);
+        }
+        aggregator.flush_all();
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let partials = aggregator.collect_available();
+        assert!(!partials.is_empty());
+
+        let merged = multiway_collect(partials);
+        assert!(merged.contains_key(&key_a));
+        assert!(merged.contains_key(&key_b));
+        assert_eq!(merged[&key_a].count, 5);
+        assert_eq!(merged[&key_b].count, 5);
+
+        aggregator.shutdown();
+    }
 }
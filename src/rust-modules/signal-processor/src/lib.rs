@@ -10,6 +10,7 @@
 //! - **异常检测**: Z-Score、IQR、MAD 等算法
 //! - **特征提取**: 时域和频域特征提取
 //! - **并行处理**: 利用 Rayon 进行并行计算
+//! - **精度切换**: 通过 `f32` Cargo feature 在 `f32`/`f64` 间切换（默认 `f64`）
 //!
 //! # 示例
 //!
@@ -29,9 +30,25 @@ pub mod features;
 pub mod window;
 
 use serde::{Deserialize, Serialize};
-use std::f64::consts::PI;
+use std::collections::VecDeque;
 use thiserror::Error;
 
+// ============================================
+// 精度开关
+// ============================================
+
+/// 信号处理所使用的浮点精度，由 Cargo feature `f32` 选择（默认 `f64`）。
+/// 切换该 feature 可在内存受限的边缘设备上将内存占用减半，而无需改动调用方代码。
+#[cfg(feature = "f32")]
+pub type Flt = f32;
+#[cfg(not(feature = "f32"))]
+pub type Flt = f64;
+
+#[cfg(feature = "f32")]
+pub const PI: Flt = std::f32::consts::PI;
+#[cfg(not(feature = "f32"))]
+pub const PI: Flt = std::f64::consts::PI;
+
 // ============================================
 // 错误类型
 // ============================================
@@ -43,7 +60,7 @@ pub enum SignalError {
     InsufficientLength { required: usize, actual: usize },
 
     #[error("无效的采样率: {0}")]
-    InvalidSampleRate(f64),
+    InvalidSampleRate(Flt),
 
     #[error("无效的滤波器参数: {0}")]
     InvalidFilterParams(String),
@@ -57,6 +74,284 @@ pub enum SignalError {
 
 pub type Result<T> = std::result::Result<T, SignalError>;
 
+// ============================================
+// Biquad 滤波器
+// ============================================
+
+/// 二阶节（biquad）滤波器，使用标准的 Direct-Form-I 差分方程：
+/// `y[n] = b0·x[n] + b1·x[n-1] + b2·x[n-2] − a1·y[n-1] − a2·y[n-2]`（`a0` 已归一化为 1）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Biquad {
+    pub b0: Flt,
+    pub b1: Flt,
+    pub b2: Flt,
+    pub a1: Flt,
+    pub a2: Flt,
+    x1: Flt,
+    x2: Flt,
+    y1: Flt,
+    y2: Flt,
+}
+
+impl Biquad {
+    /// 根据系数创建一个新的二阶节（初始延迟寄存器为零）
+    pub fn new(b0: Flt, b1: Flt, b2: Flt, a1: Flt, a2: Flt) -> Self {
+        Self {
+            b0,
+            b1,
+            b2,
+            a1,
+            a2,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    /// 重置延迟寄存器（`x[n-1], x[n-2], y[n-1], y[n-2]`）
+    pub fn reset(&mut self) {
+        self.x1 = 0.0;
+        self.x2 = 0.0;
+        self.y1 = 0.0;
+        self.y2 = 0.0;
+    }
+
+    /// 处理单个采样点，保持内部状态
+    pub fn process_sample(&mut self, x0: Flt) -> Flt {
+        let y0 = self.b0 * x0 + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y0;
+
+        y0
+    }
+
+    /// 整段信号滤波（使用并更新内部状态）
+    pub fn process(&mut self, signal: &[Flt]) -> Vec<Flt> {
+        signal.iter().map(|&x| self.process_sample(x)).collect()
+    }
+}
+
+/// 多个二阶节级联而成的滤波器，用于实现任意阶数的 IIR 滤波器
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BiquadCascade {
+    sections: Vec<Biquad>,
+}
+
+impl BiquadCascade {
+    /// 从一组二阶节创建级联滤波器
+    pub fn new(sections: Vec<Biquad>) -> Self {
+        Self { sections }
+    }
+
+    /// 级联的阶数（每个二阶节贡献 2 阶）
+    pub fn order(&self) -> usize {
+        self.sections.len() * 2
+    }
+
+    /// 重置所有二阶节的延迟寄存器
+    pub fn reset(&mut self) {
+        for section in &mut self.sections {
+            section.reset();
+        }
+    }
+
+    /// 单采样点处理，依次流经每个二阶节
+    pub fn process_sample(&mut self, x0: Flt) -> Flt {
+        self.sections
+            .iter_mut()
+            .fold(x0, |sample, section| section.process_sample(sample))
+    }
+
+    /// 整段信号滤波（正向一次）
+    pub fn process(&mut self, signal: &[Flt]) -> Vec<Flt> {
+        signal.iter().map(|&x| self.process_sample(x)).collect()
+    }
+
+    /// 零相位滤波：正向滤波后再反向滤波一次，消除相位失真。
+    /// 在信号两端各镜像 `3·order` 个采样点以抑制边缘暂态。
+    pub fn filtfilt(&self, signal: &[Flt]) -> Result<Vec<Flt>> {
+        let edge = 3 * self.order();
+        if signal.len() <= edge {
+            return Err(SignalError::InsufficientLength {
+                required: edge + 1,
+                actual: signal.len(),
+            });
+        }
+
+        // 两端镜像延拓，减小起始/终止暂态
+        let mut extended = Vec::with_capacity(signal.len() + 2 * edge);
+        extended.extend(signal[1..=edge].iter().rev().map(|&v| 2.0 * signal[0] - v));
+        extended.extend_from_slice(signal);
+        extended.extend(
+            signal[signal.len() - edge - 1..signal.len() - 1]
+                .iter()
+                .rev()
+                .map(|&v| 2.0 * signal[signal.len() - 1] - v),
+        );
+
+        let mut forward_cascade = self.clone();
+        forward_cascade.reset();
+        let forward = forward_cascade.process(&extended);
+
+        let mut backward_cascade = self.clone();
+        backward_cascade.reset();
+        let mut backward: Vec<Flt> = forward
+            .iter()
+            .rev()
+            .map(|&x| backward_cascade.process_sample(x))
+            .collect();
+        backward.reverse();
+
+        Ok(backward[edge..edge + signal.len()].to_vec())
+    }
+}
+
+/// Butterworth 模拟原型极点（s 平面，单位圆上），经预畸变与双线性变换后
+/// 生成一组二阶节系数
+fn butterworth_lowpass_sections(order: usize, cutoff: Flt, sample_rate: Flt) -> Result<Vec<Biquad>> {
+    if order == 0 {
+        return Err(SignalError::InvalidFilterParams("滤波器阶数必须大于 0".to_string()));
+    }
+    if cutoff <= 0.0 || cutoff >= sample_rate / 2.0 {
+        return Err(SignalError::InvalidFilterParams(
+            "截止频率必须在 (0, Nyquist) 范围内".to_string(),
+        ));
+    }
+
+    let omega_c = prewarped_omega_c(cutoff, sample_rate);
+
+    let mut sections = Vec::with_capacity((order + 1) / 2);
+    let pairs = order / 2;
+
+    for k in 0..pairs {
+        // 模拟原型极点角度 θ_k = π/2 + (2k+1)π/(2N)，取共轭对中的上半部分
+        let theta = PI / 2.0 + (2.0 * k as Flt + 1.0) * PI / (2.0 * order as Flt);
+        let real = omega_c * theta.cos();
+        let imag = omega_c * theta.sin();
+
+        // 双线性变换： s = 2·fs·(z-1)/(z+1)，对共轭极点对 (real ± j·imag) 求解二阶节系数
+        sections.push(conjugate_pole_pair_to_biquad(real, imag, omega_c, sample_rate));
+    }
+
+    if order % 2 == 1 {
+        // 奇数阶还有一个实极点，退化为一阶段，用 b2=a2=0 的二阶节表示
+        sections.push(real_pole_to_biquad(omega_c, sample_rate));
+    }
+
+    Ok(sections)
+}
+
+/// 频率预畸变：模拟角频率 Ω = 2·fs·tan(π·fc/fs)
+fn prewarped_omega_c(cutoff: Flt, sample_rate: Flt) -> Flt {
+    2.0 * sample_rate * (PI * cutoff / sample_rate).tan()
+}
+
+/// 将一对共轭极点（实部 `re`，虚部 `im`，归一化增益为直流增益 1）经双线性变换转换为二阶节
+fn conjugate_pole_pair_to_biquad(re: Flt, im: Flt, omega_c: Flt, sample_rate: Flt) -> Biquad {
+    let fs2 = 2.0 * sample_rate;
+    // 模拟原型传递函数: H(s) = ωc^2 / (s^2 - 2·re·s + (re^2+im^2))
+    let pole_mag_sq = re * re + im * im;
+
+    // 双线性变换代入 s = fs2 * (z-1)/(z+1)，整理得到分母/分子系数
+    let a0 = fs2 * fs2 - 2.0 * re * fs2 + pole_mag_sq;
+    let a1 = 2.0 * (pole_mag_sq - fs2 * fs2);
+    let a2 = fs2 * fs2 + 2.0 * re * fs2 + pole_mag_sq;
+    let b_common = omega_c * omega_c;
+
+    Biquad::new(
+        b_common / a0,
+        2.0 * b_common / a0,
+        b_common / a0,
+        a1 / a0,
+        a2 / a0,
+    )
+}
+
+/// 奇数阶 Butterworth 的实极点段（s = -ωc）
+fn real_pole_to_biquad(omega_c: Flt, sample_rate: Flt) -> Biquad {
+    let fs2 = 2.0 * sample_rate;
+    let a0 = fs2 + omega_c;
+    let a1 = omega_c - fs2;
+    let b0 = omega_c / a0;
+    let b1 = omega_c / a0;
+
+    Biquad::new(b0, b1, 0.0, a1 / a0, 0.0)
+}
+
+/// 低通 → 高通的频谱变换：将每个低通二阶节替换为对应的高通二阶节（s → ωc^2/s 的离散等效）
+///
+/// 分母（极点）在低通/高通之间保持不变，只有分子需要重新设计：低通分子是
+/// `ωc^2`（二阶节）或 `ωc`（奇数阶退化出的一阶节），高通分子对应改为
+/// `fs2^2`/`fs2`，因此这里按 `(fs2/ωc)^n` 重新缩放分子（`n` 由节的阶数决定，
+/// 通过 `b2` 是否为零判断），而不是直接照抄低通分子导致通带增益偏低。
+fn lowpass_sections_to_highpass(sections: &[Biquad], omega_c: Flt, sample_rate: Flt) -> Vec<Biquad> {
+    let fs2 = 2.0 * sample_rate;
+    sections
+        .iter()
+        .map(|s| {
+            let scale = if s.b2 == 0.0 {
+                fs2 / omega_c
+            } else {
+                (fs2 / omega_c) * (fs2 / omega_c)
+            };
+            // 低通 b0=b2, b1=2·b0，高通对应 b0'=b0·scale, b1'=-2·b0'，b2'=b2·scale（对称取反）
+            Biquad::new(s.b0 * scale, -s.b1 * scale, s.b2 * scale, s.a1, s.a2)
+        })
+        .collect()
+}
+
+/// RBJ Audio EQ Cookbook 带通滤波器（恒定 0dB 峰值增益），由中心频率与 Q 值设计
+fn design_bandpass_q_biquad(center: Flt, q: Flt, sample_rate: Flt) -> Result<Biquad> {
+    if center <= 0.0 || center >= sample_rate / 2.0 || q <= 0.0 {
+        return Err(SignalError::InvalidFilterParams(
+            "中心频率必须在 (0, Nyquist) 范围内，且 Q 必须大于 0".to_string(),
+        ));
+    }
+
+    let omega = 2.0 * PI * center / sample_rate;
+    let sn = omega.sin();
+    let cs = omega.cos();
+    let alpha = sn / (2.0 * q);
+
+    let a0 = 1.0 + alpha;
+    Ok(Biquad::new(
+        alpha / a0,
+        0.0,
+        -alpha / a0,
+        -2.0 * cs / a0,
+        (1.0 - alpha) / a0,
+    ))
+}
+
+/// RBJ Audio EQ Cookbook 陷波（notch）滤波器，由中心频率与 Q 值设计
+fn design_notch_biquad(center: Flt, q: Flt, sample_rate: Flt) -> Result<Biquad> {
+    if center <= 0.0 || center >= sample_rate / 2.0 || q <= 0.0 {
+        return Err(SignalError::InvalidFilterParams(
+            "中心频率必须在 (0, Nyquist) 范围内，且 Q 必须大于 0".to_string(),
+        ));
+    }
+
+    let omega = 2.0 * PI * center / sample_rate;
+    let sn = omega.sin();
+    let cs = omega.cos();
+    let alpha = sn / (2.0 * q);
+
+    let a0 = 1.0 + alpha;
+    Ok(Biquad::new(
+        1.0 / a0,
+        -2.0 * cs / a0,
+        1.0 / a0,
+        -2.0 * cs / a0,
+        (1.0 - alpha) / a0,
+    ))
+}
+
 // ============================================
 // 核心类型定义
 // ============================================
@@ -65,7 +360,7 @@ pub type Result<T> = std::result::Result<T, SignalError>;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataPoint {
     pub timestamp: i64,
-    pub value: f64,
+    pub value: Flt,
     pub quality: DataQuality,
 }
 
@@ -81,19 +376,25 @@ pub enum DataQuality {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FilterType {
     /// 低通滤波器
-    LowPass { cutoff: f64 },
+    LowPass { cutoff: Flt },
     /// 高通滤波器
-    HighPass { cutoff: f64 },
+    HighPass { cutoff: Flt },
     /// 带通滤波器
-    BandPass { low: f64, high: f64 },
+    BandPass { low: Flt, high: Flt },
     /// 带阻滤波器
-    BandStop { low: f64, high: f64 },
+    BandStop { low: Flt, high: Flt },
     /// 移动平均滤波器
     MovingAverage { window_size: usize },
     /// 指数移动平均
-    ExponentialMovingAverage { alpha: f64 },
+    ExponentialMovingAverage { alpha: Flt },
     /// 中值滤波器
     Median { window_size: usize },
+    /// 恒定 0dB 峰值增益的二阶带通滤波器（RBJ biquad），以中心频率与 Q 值指定
+    BandPassQ { center: Flt, q: Flt },
+    /// 陷波（notch）滤波器（RBJ biquad），以中心频率与 Q 值指定
+    Notch { center: Flt, q: Flt },
+    /// 指定阶数的 Butterworth 低通滤波器（级联 `order/2` 个二阶节，奇数阶时另加一个一阶节）
+    ButterworthLowPass { cutoff: Flt, order: usize },
 }
 
 /// 窗函数类型
@@ -108,53 +409,97 @@ pub enum WindowType {
     /// 布莱克曼窗
     Blackman,
     /// 凯泽窗
-    Kaiser { beta: f64 },
+    Kaiser { beta: Flt },
     /// 高斯窗
-    Gaussian { sigma: f64 },
+    Gaussian { sigma: Flt },
 }
 
 /// 统计结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatisticsResult {
     pub count: usize,
-    pub mean: f64,
-    pub variance: f64,
-    pub std_dev: f64,
-    pub min: f64,
-    pub max: f64,
-    pub range: f64,
-    pub median: f64,
-    pub q1: f64,
-    pub q3: f64,
-    pub iqr: f64,
-    pub skewness: f64,
-    pub kurtosis: f64,
-    pub rms: f64,
-    pub peak_to_peak: f64,
-    pub crest_factor: f64,
+    pub mean: Flt,
+    pub variance: Flt,
+    pub std_dev: Flt,
+    pub min: Flt,
+    pub max: Flt,
+    pub range: Flt,
+    pub median: Flt,
+    pub q1: Flt,
+    pub q3: Flt,
+    pub iqr: Flt,
+    pub skewness: Flt,
+    pub kurtosis: Flt,
+    pub rms: Flt,
+    pub peak_to_peak: Flt,
+    pub crest_factor: Flt,
 }
 
 /// FFT 结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FftResult {
-    pub frequencies: Vec<f64>,
-    pub magnitudes: Vec<f64>,
-    pub phases: Vec<f64>,
-    pub power_spectrum: Vec<f64>,
-    pub dominant_frequency: f64,
-    pub total_power: f64,
+    pub frequencies: Vec<Flt>,
+    pub magnitudes: Vec<Flt>,
+    pub phases: Vec<Flt>,
+    pub power_spectrum: Vec<Flt>,
+    pub dominant_frequency: Flt,
+    pub total_power: Flt,
+}
+
+/// Welch 法功率谱密度估计结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PsdResult {
+    pub frequencies: Vec<Flt>,
+    pub psd: Vec<Flt>,
+    pub resolution: Flt,
+}
+
+/// 单边功率谱（`power_spectrum_welch` 的返回类型，是 `PsdResult` 的精简别名形式）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerSpectrum {
+    pub frequencies: Vec<Flt>,
+    pub power: Vec<Flt>,
 }
 
 /// 异常检测结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnomalyResult {
     pub is_anomaly: bool,
-    pub score: f64,
-    pub threshold: f64,
+    pub score: Flt,
+    pub threshold: Flt,
     pub method: String,
     pub details: Option<String>,
 }
 
+/// 基频（音高）检测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PitchResult {
+    /// 自相关法估计的基频（Hz）
+    pub autocorrelation_freq: Option<Flt>,
+    /// 谐波积谱（HPS）法估计的基频（Hz）
+    pub hps_freq: Option<Flt>,
+    /// 两种估计一致程度的置信度（0-1，相对误差越小越接近 1）
+    pub confidence: Flt,
+}
+
+/// 匹配滤波结果中的单个检测位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchedFilterDetection {
+    /// 信号中的起始位置（样本索引）
+    pub lag: usize,
+    /// 该位置的归一化互相关系数
+    pub correlation: Flt,
+}
+
+/// 匹配滤波（归一化互相关）结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchedFilterResult {
+    /// 每个滑动位置的归一化互相关系数
+    pub correlation: Vec<Flt>,
+    /// 超过阈值的检测位置（按相关系数降序不做排序，按位置先后给出）
+    pub detections: Vec<MatchedFilterDetection>,
+}
+
 /// 特征提取结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureSet {
@@ -167,28 +512,39 @@ pub struct FeatureSet {
 /// 时域特征
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeDomainFeatures {
-    pub mean: f64,
-    pub std_dev: f64,
-    pub rms: f64,
-    pub peak: f64,
-    pub peak_to_peak: f64,
-    pub crest_factor: f64,
-    pub shape_factor: f64,
-    pub impulse_factor: f64,
-    pub clearance_factor: f64,
+    pub mean: Flt,
+    pub std_dev: Flt,
+    pub rms: Flt,
+    pub peak: Flt,
+    pub peak_to_peak: Flt,
+    pub crest_factor: Flt,
+    pub shape_factor: Flt,
+    pub impulse_factor: Flt,
+    pub clearance_factor: Flt,
     pub zero_crossings: usize,
+    /// 过零率（每秒过零次数）
+    pub zero_crossing_rate: Flt,
 }
 
 /// 频域特征
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrequencyDomainFeatures {
-    pub dominant_frequency: f64,
-    pub spectral_centroid: f64,
-    pub spectral_bandwidth: f64,
-    pub spectral_rolloff: f64,
-    pub spectral_flatness: f64,
-    pub spectral_entropy: f64,
-    pub band_powers: Vec<f64>,
+    pub dominant_frequency: Flt,
+    pub spectral_centroid: Flt,
+    pub spectral_bandwidth: Flt,
+    pub spectral_rolloff: Flt,
+    pub spectral_flatness: Flt,
+    pub spectral_entropy: Flt,
+    pub band_powers: Vec<Flt>,
+}
+
+/// MFCC（梅尔频率倒谱系数）提取结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfccResult {
+    /// 梅尔滤波器组能量（对数域）
+    pub mel_log_energies: Vec<Flt>,
+    /// 经 DCT-II 变换后保留的倒谱系数
+    pub mfcc: Vec<Flt>,
 }
 
 // ============================================
@@ -197,13 +553,13 @@ pub struct FrequencyDomainFeatures {
 
 /// 信号处理器
 pub struct SignalProcessor {
-    sample_rate: f64,
+    sample_rate: Flt,
     fft_size: usize,
 }
 
 impl SignalProcessor {
     /// 创建新的信号处理器
-    pub fn new(sample_rate: f64) -> Result<Self> {
+    pub fn new(sample_rate: Flt) -> Result<Self> {
         if sample_rate <= 0.0 {
             return Err(SignalError::InvalidSampleRate(sample_rate));
         }
@@ -220,7 +576,7 @@ impl SignalProcessor {
     }
 
     /// 获取采样率
-    pub fn sample_rate(&self) -> f64 {
+    pub fn sample_rate(&self) -> Flt {
         self.sample_rate
     }
 
@@ -229,7 +585,7 @@ impl SignalProcessor {
     // ============================================
 
     /// 应用滤波器
-    pub fn apply_filter(&self, signal: &[f64], filter_type: FilterType) -> Result<Vec<f64>> {
+    pub fn apply_filter(&self, signal: &[Flt], filter_type: FilterType) -> Result<Vec<Flt>> {
         match filter_type {
             FilterType::MovingAverage { window_size } => {
                 self.moving_average(signal, window_size)
@@ -252,11 +608,22 @@ impl SignalProcessor {
             FilterType::BandStop { low, high } => {
                 self.butterworth_bandstop(signal, low, high, 4)
             }
+            FilterType::BandPassQ { center, q } => {
+                let biquad = design_bandpass_q_biquad(center, q, self.sample_rate)?;
+                BiquadCascade::new(vec![biquad]).filtfilt(signal)
+            }
+            FilterType::Notch { center, q } => {
+                let biquad = design_notch_biquad(center, q, self.sample_rate)?;
+                BiquadCascade::new(vec![biquad]).filtfilt(signal)
+            }
+            FilterType::ButterworthLowPass { cutoff, order } => {
+                self.butterworth_lowpass(signal, cutoff, order)
+            }
         }
     }
 
     /// 移动平均滤波
-    fn moving_average(&self, signal: &[f64], window_size: usize) -> Result<Vec<f64>> {
+    fn moving_average(&self, signal: &[Flt], window_size: usize) -> Result<Vec<Flt>> {
         if signal.len() < window_size {
             return Err(SignalError::InsufficientLength {
                 required: window_size,
@@ -265,21 +632,21 @@ impl SignalProcessor {
         }
 
         let mut result = Vec::with_capacity(signal.len());
-        let mut sum: f64 = signal[..window_size].iter().sum();
+        let mut sum: Flt = signal[..window_size].iter().sum();
         
         for i in 0..signal.len() {
             if i >= window_size {
                 sum -= signal[i - window_size];
                 sum += signal[i];
             }
-            result.push(sum / window_size as f64);
+            result.push(sum / window_size as Flt);
         }
 
         Ok(result)
     }
 
     /// 指数移动平均滤波
-    fn exponential_moving_average(&self, signal: &[f64], alpha: f64) -> Result<Vec<f64>> {
+    fn exponential_moving_average(&self, signal: &[Flt], alpha: Flt) -> Result<Vec<Flt>> {
         if alpha <= 0.0 || alpha > 1.0 {
             return Err(SignalError::InvalidFilterParams(
                 format!("alpha 必须在 (0, 1] 范围内，实际值: {}", alpha)
@@ -298,7 +665,7 @@ impl SignalProcessor {
     }
 
     /// 中值滤波
-    fn median_filter(&self, signal: &[f64], window_size: usize) -> Result<Vec<f64>> {
+    fn median_filter(&self, signal: &[Flt], window_size: usize) -> Result<Vec<Flt>> {
         if signal.len() < window_size {
             return Err(SignalError::InsufficientLength {
                 required: window_size,
@@ -312,7 +679,7 @@ impl SignalProcessor {
         for i in 0..signal.len() {
             let start = i.saturating_sub(half_window);
             let end = (i + half_window + 1).min(signal.len());
-            let mut window: Vec<f64> = signal[start..end].to_vec();
+            let mut window: Vec<Flt> = signal[start..end].to_vec();
             window.sort_by(|a, b| a.partial_cmp(b).unwrap());
             result.push(window[window.len() / 2]);
         }
@@ -320,57 +687,62 @@ impl SignalProcessor {
         Ok(result)
     }
 
-    /// Butterworth 低通滤波器
-    fn butterworth_lowpass(&self, signal: &[f64], cutoff: f64, order: usize) -> Result<Vec<f64>> {
-        let normalized_cutoff = cutoff / (self.sample_rate / 2.0);
-        if normalized_cutoff <= 0.0 || normalized_cutoff >= 1.0 {
-            return Err(SignalError::InvalidFilterParams(
-                format!("截止频率必须在 (0, Nyquist) 范围内")
-            ));
-        }
-
-        // 简化的 IIR 滤波实现
-        let rc = 1.0 / (2.0 * PI * cutoff);
-        let dt = 1.0 / self.sample_rate;
-        let alpha = dt / (rc + dt);
+    /// 构造 Butterworth 低通二阶节级联
+    fn design_butterworth_lowpass(&self, cutoff: Flt, order: usize) -> Result<BiquadCascade> {
+        Ok(BiquadCascade::new(butterworth_lowpass_sections(
+            order,
+            cutoff,
+            self.sample_rate,
+        )?))
+    }
 
-        let mut result = vec![0.0; signal.len()];
-        result[0] = signal[0];
-        
-        for _ in 0..order {
-            for i in 1..signal.len() {
-                result[i] = alpha * signal[i] + (1.0 - alpha) * result[i - 1];
-            }
-        }
+    /// 构造 Butterworth 高通二阶节级联（低通 → 高通频谱变换）
+    fn design_butterworth_highpass(&self, cutoff: Flt, order: usize) -> Result<BiquadCascade> {
+        let lowpass_sections = butterworth_lowpass_sections(order, cutoff, self.sample_rate)?;
+        let omega_c = prewarped_omega_c(cutoff, self.sample_rate);
+        Ok(BiquadCascade::new(lowpass_sections_to_highpass(
+            &lowpass_sections,
+            omega_c,
+            self.sample_rate,
+        )))
+    }
 
-        Ok(result)
+    /// Butterworth 低通滤波器（真实双二阶级联，零相位 filtfilt）
+    fn butterworth_lowpass(&self, signal: &[Flt], cutoff: Flt, order: usize) -> Result<Vec<Flt>> {
+        let cascade = self.design_butterworth_lowpass(cutoff, order)?;
+        cascade.filtfilt(signal)
     }
 
-    /// Butterworth 高通滤波器
-    fn butterworth_highpass(&self, signal: &[f64], cutoff: f64, order: usize) -> Result<Vec<f64>> {
-        let lowpass = self.butterworth_lowpass(signal, cutoff, order)?;
-        Ok(signal.iter().zip(lowpass.iter()).map(|(s, l)| s - l).collect())
+    /// Butterworth 高通滤波器（真实双二阶级联，零相位 filtfilt）
+    fn butterworth_highpass(&self, signal: &[Flt], cutoff: Flt, order: usize) -> Result<Vec<Flt>> {
+        let cascade = self.design_butterworth_highpass(cutoff, order)?;
+        cascade.filtfilt(signal)
     }
 
-    /// Butterworth 带通滤波器
-    fn butterworth_bandpass(&self, signal: &[f64], low: f64, high: f64, order: usize) -> Result<Vec<f64>> {
+    /// Butterworth 带通滤波器（高通级联低通，均为零相位）
+    fn butterworth_bandpass(&self, signal: &[Flt], low: Flt, high: Flt, order: usize) -> Result<Vec<Flt>> {
         let highpassed = self.butterworth_highpass(signal, low, order)?;
         self.butterworth_lowpass(&highpassed, high, order)
     }
 
-    /// Butterworth 带阻滤波器
-    fn butterworth_bandstop(&self, signal: &[f64], low: f64, high: f64, order: usize) -> Result<Vec<f64>> {
+    /// Butterworth 带阻滤波器（低通与高通结果相加）
+    fn butterworth_bandstop(&self, signal: &[Flt], low: Flt, high: Flt, order: usize) -> Result<Vec<Flt>> {
         let lowpassed = self.butterworth_lowpass(signal, low, order)?;
         let highpassed = self.butterworth_highpass(signal, high, order)?;
         Ok(lowpassed.iter().zip(highpassed.iter()).map(|(l, h)| l + h).collect())
     }
 
+    /// 对外暴露的零相位滤波接口：构造指定阶数的 Butterworth 低通级联并执行 `filtfilt`
+    pub fn filtfilt_lowpass(&self, signal: &[Flt], cutoff: Flt, order: usize) -> Result<Vec<Flt>> {
+        self.butterworth_lowpass(signal, cutoff, order)
+    }
+
     // ============================================
     // FFT 分析
     // ============================================
 
     /// 执行 FFT 分析
-    pub fn fft_analysis(&self, signal: &[f64]) -> Result<FftResult> {
+    pub fn fft_analysis(&self, signal: &[Flt]) -> Result<FftResult> {
         use rustfft::{FftPlanner, num_complex::Complex};
 
         let n = signal.len().next_power_of_two();
@@ -378,7 +750,7 @@ impl SignalProcessor {
         let fft = planner.plan_fft_forward(n);
 
         // 准备输入数据（零填充）
-        let mut buffer: Vec<Complex<f64>> = signal
+        let mut buffer: Vec<Complex<Flt>> = signal
             .iter()
             .map(|&x| Complex::new(x, 0.0))
             .collect();
@@ -388,7 +760,7 @@ impl SignalProcessor {
         fft.process(&mut buffer);
 
         // 计算频率、幅度和相位
-        let freq_resolution = self.sample_rate / n as f64;
+        let freq_resolution = self.sample_rate / n as Flt;
         let half_n = n / 2;
 
         let mut frequencies = Vec::with_capacity(half_n);
@@ -401,8 +773,8 @@ impl SignalProcessor {
         let mut total_power = 0.0;
 
         for i in 0..half_n {
-            let freq = i as f64 * freq_resolution;
-            let magnitude = buffer[i].norm() * 2.0 / n as f64;
+            let freq = i as Flt * freq_resolution;
+            let magnitude = buffer[i].norm() * 2.0 / n as Flt;
             let phase = buffer[i].arg();
             let power = magnitude * magnitude;
 
@@ -429,12 +801,371 @@ impl SignalProcessor {
         })
     }
 
+    /// Welch 法功率谱密度估计：将信号切分为重叠段，加窗后逐段 FFT，
+    /// 对周期图取平均以降低频谱方差
+    pub fn power_spectral_density(
+        &self,
+        signal: &[Flt],
+        segment_len: usize,
+        overlap: Flt,
+        window_type: WindowType,
+    ) -> Result<PsdResult> {
+        use rustfft::{num_complex::Complex, FftPlanner};
+
+        if segment_len == 0 || signal.len() < segment_len {
+            return Err(SignalError::InsufficientLength {
+                required: segment_len,
+                actual: signal.len(),
+            });
+        }
+        if !(0.0..1.0).contains(&overlap) {
+            return Err(SignalError::InvalidFilterParams(format!(
+                "重叠率必须在 [0, 1) 范围内，实际值: {}",
+                overlap
+            )));
+        }
+
+        let n = segment_len.next_power_of_two();
+        let hop = ((segment_len as Flt) * (1.0 - overlap)).round().max(1.0) as usize;
+
+        let window = self.apply_window(&vec![1.0; segment_len], window_type);
+        // 窗函数功率归一化因子 U = Σ w[i]^2
+        let window_power: Flt = window.iter().map(|w| w * w).sum();
+
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(n);
+        let half_n = n / 2;
+
+        let mut psd_sum = vec![0.0; half_n];
+        let mut segment_count = 0usize;
+
+        let mut start = 0;
+        while start + segment_len <= signal.len() {
+            let segment = &signal[start..start + segment_len];
+            let windowed: Vec<Flt> = segment
+                .iter()
+                .zip(window.iter())
+                .map(|(s, w)| s * w)
+                .collect();
+
+            let mut buffer: Vec<Complex<Flt>> =
+                windowed.iter().map(|&x| Complex::new(x, 0.0)).collect();
+            buffer.resize(n, Complex::new(0.0, 0.0));
+            fft.process(&mut buffer);
+
+            for i in 0..half_n {
+                let magnitude_sq = buffer[i].norm_sqr();
+                // 单边谱：除直流外的正频率分量计入对应负频率的能量（×2）
+                let one_sided = if i == 0 { 1.0 } else { 2.0 };
+                psd_sum[i] += one_sided * magnitude_sq / (self.sample_rate * window_power);
+            }
+
+            segment_count += 1;
+            start += hop;
+        }
+
+        if segment_count == 0 {
+            return Err(SignalError::InsufficientLength {
+                required: segment_len,
+                actual: signal.len(),
+            });
+        }
+
+        let resolution = self.sample_rate / n as Flt;
+        let frequencies: Vec<Flt> = (0..half_n).map(|i| i as Flt * resolution).collect();
+        let psd: Vec<Flt> = psd_sum.iter().map(|&p| p / segment_count as Flt).collect();
+
+        Ok(PsdResult {
+            frequencies,
+            psd,
+            resolution,
+        })
+    }
+
+    /// Welch 法功率谱估计的简化接口，直接返回频率/功率两列，便于不需要频率分辨率的调用方使用
+    pub fn power_spectrum_welch(
+        &self,
+        signal: &[Flt],
+        segment_len: usize,
+        overlap: Flt,
+        window: WindowType,
+    ) -> Result<PowerSpectrum> {
+        let psd = self.power_spectral_density(signal, segment_len, overlap, window)?;
+        Ok(PowerSpectrum {
+            frequencies: psd.frequencies,
+            power: psd.psd,
+        })
+    }
+
+    /// 基频（音高）检测：结合自相关法与谐波积谱（HPS）法，返回两个独立估计及置信度
+    pub fn fundamental_frequency(&self, signal: &[Flt]) -> Result<PitchResult> {
+        if signal.len() < 2 {
+            return Err(SignalError::InsufficientLength {
+                required: 2,
+                actual: signal.len(),
+            });
+        }
+
+        let min_freq = 20.0;
+        let autocorrelation_freq = self.autocorrelation_pitch(signal, min_freq);
+        let hps_freq = self.harmonic_product_spectrum_pitch(signal)?;
+
+        let confidence = match (autocorrelation_freq, hps_freq) {
+            (Some(a), Some(h)) if a > 0.0 || h > 0.0 => {
+                let rel_err = (a - h).abs() / a.max(h).max(1e-10);
+                (1.0 - rel_err).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        };
+
+        Ok(PitchResult {
+            autocorrelation_freq,
+            hps_freq,
+            confidence,
+        })
+    }
+
+    /// 纯自相关法基频检测：只返回自相关估计，信号能量过低（静音）或自相关
+    /// 从不跌落到零以下（非周期信号）时返回 `None`
+    pub fn autocorrelation_fundamental_frequency(&self, signal: &[Flt]) -> Result<Option<Flt>> {
+        if signal.len() < 2 {
+            return Err(SignalError::InsufficientLength {
+                required: 2,
+                actual: signal.len(),
+            });
+        }
+
+        Ok(self.autocorrelation_pitch(signal, self.sample_rate / signal.len() as Flt))
+    }
+
+    /// 自相关法估计基频：在相关值首次降为负值之后、`max_lag` 之前的延迟范围内取最大相关值
+    /// 对应的延迟，并用抛物线插值细化
+    fn autocorrelation_pitch(&self, signal: &[Flt], min_freq: Flt) -> Option<Flt> {
+        let max_lag = (self.sample_rate / min_freq).round() as usize;
+        let max_lag = max_lag.min(signal.len() - 1);
+        if max_lag < 2 {
+            return None;
+        }
+
+        let mean = signal.iter().sum::<Flt>() / signal.len() as Flt;
+        let centered: Vec<Flt> = signal.iter().map(|x| x - mean).collect();
+
+        let r0: Flt = centered.iter().map(|x| x * x).sum();
+        if r0 < 1e-12 {
+            return None;
+        }
+
+        let autocorr = |lag: usize| -> Flt {
+            centered[..centered.len() - lag]
+                .iter()
+                .zip(&centered[lag..])
+                .map(|(a, b)| a * b)
+                .sum::<Flt>()
+        };
+
+        // 跳过主瓣，找到相关性首次降为负值的位置
+        let mut k = 1;
+        while k <= max_lag && autocorr(k) >= 0.0 {
+            k += 1;
+        }
+        if k > max_lag {
+            return None;
+        }
+
+        // 从该处起寻找最大相关值对应的延迟
+        let mut best_lag = k;
+        let mut best_val = Flt::NEG_INFINITY;
+        for lag in k..=max_lag {
+            let val = autocorr(lag);
+            if val > best_val {
+                best_val = val;
+                best_lag = lag;
+            }
+        }
+
+        if best_val <= 0.0 || best_lag == 0 {
+            return None;
+        }
+
+        // 抛物线插值细化峰值位置，获得亚采样精度
+        let refined_lag = if best_lag > 1 && best_lag < max_lag {
+            let y_prev = autocorr(best_lag - 1);
+            let y_curr = best_val;
+            let y_next = autocorr(best_lag + 1);
+            let denom = y_prev - 2.0 * y_curr + y_next;
+            if denom.abs() > 1e-12 {
+                best_lag as Flt + 0.5 * (y_prev - y_next) / denom
+            } else {
+                best_lag as Flt
+            }
+        } else {
+            best_lag as Flt
+        };
+
+        Some(self.sample_rate / refined_lag)
+    }
+
+    /// 谐波积谱（HPS）法估计基频：对幅度谱做整数倍降采样并逐点相乘
+    fn harmonic_product_spectrum_pitch(&self, signal: &[Flt]) -> Result<Option<Flt>> {
+        let fft = self.fft_analysis(signal)?;
+        let magnitudes = &fft.magnitudes;
+        if magnitudes.is_empty() {
+            return Ok(None);
+        }
+
+        let harmonics = [2usize, 3, 4, 5];
+        let mut hps = magnitudes.clone();
+
+        for &factor in &harmonics {
+            for i in 0..hps.len() {
+                let src_idx = i * factor;
+                hps[i] *= if src_idx < magnitudes.len() {
+                    magnitudes[src_idx]
+                } else {
+                    0.0
+                };
+            }
+        }
+
+        let (peak_idx, peak_val) = hps
+            .iter()
+            .enumerate()
+            .skip(1)
+            .fold((0usize, 0.0 as Flt), |(bi, bv), (i, &v)| {
+                if v > bv {
+                    (i, v)
+                } else {
+                    (bi, bv)
+                }
+            });
+
+        if peak_val <= 0.0 || peak_idx == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(fft.frequencies[peak_idx]))
+    }
+
+    // ============================================
+    // 匹配滤波 / 互相关
+    // ============================================
+
+    /// 构造线性调频（chirp）模板：`sin(2π·(f0·t + 0.5·k·t²))`，频率从 `f0` 线性扫描到 `f1`
+    pub fn chirp_template(&self, f0: Flt, f1: Flt, duration_secs: Flt) -> Vec<Flt> {
+        let num_samples = (duration_secs * self.sample_rate).round().max(1.0) as usize;
+        let k = (f1 - f0) / duration_secs;
+
+        (0..num_samples)
+            .map(|i| {
+                let t = i as Flt / self.sample_rate;
+                (2.0 * PI * (f0 * t + 0.5 * k * t * t)).sin()
+            })
+            .collect()
+    }
+
+    /// 匹配滤波：对参考模板在信号上滑动，计算归一化互相关，并返回超过阈值的检测位置。
+    /// 通过 FFT 相关定理实现，运行时间近似 O(N log N)。
+    pub fn matched_filter(
+        &self,
+        signal: &[Flt],
+        template: &[Flt],
+        threshold: Flt,
+    ) -> Result<MatchedFilterResult> {
+        use rustfft::{num_complex::Complex, FftPlanner};
+
+        if template.is_empty() || signal.len() < template.len() {
+            return Err(SignalError::InsufficientLength {
+                required: template.len(),
+                actual: signal.len(),
+            });
+        }
+
+        let template_mean = template.iter().sum::<Flt>() / template.len() as Flt;
+        let template_centered: Vec<Flt> = template.iter().map(|t| t - template_mean).collect();
+        let template_std = (template_centered.iter().map(|t| t * t).sum::<Flt>()).sqrt();
+        if template_std < 1e-12 {
+            return Err(SignalError::InvalidFilterParams("模板方差为零".to_string()));
+        }
+
+        let num_positions = signal.len() - template.len() + 1;
+        let n = (signal.len() + template.len()).next_power_of_two();
+
+        let mut planner = FftPlanner::new();
+        let fft_fwd = planner.plan_fft_forward(n);
+        let fft_inv = planner.plan_fft_inverse(n);
+
+        // 信号 FFT
+        let mut signal_buf: Vec<Complex<Flt>> =
+            signal.iter().map(|&x| Complex::new(x, 0.0)).collect();
+        signal_buf.resize(n, Complex::new(0.0, 0.0));
+        fft_fwd.process(&mut signal_buf);
+
+        // 时间反转并零填充的模板 FFT（与信号做相关等价于与反转模板做卷积）
+        let mut template_buf: Vec<Complex<Flt>> = template_centered
+            .iter()
+            .rev()
+            .map(|&x| Complex::new(x, 0.0))
+            .collect();
+        template_buf.resize(n, Complex::new(0.0, 0.0));
+        fft_fwd.process(&mut template_buf);
+
+        let mut product: Vec<Complex<Flt>> = signal_buf
+            .iter()
+            .zip(template_buf.iter())
+            .map(|(s, t)| s * t)
+            .collect();
+        fft_inv.process(&mut product);
+
+        let template_len = template.len();
+        let mut correlation = Vec::with_capacity(num_positions);
+
+        // 滑动窗口均值/方差：逐位置以 O(1) 维护信号局部窗口的和与平方和，
+        // 离差平方和 Σ(x-x̄)² = Σx² - (Σx)²/n，避免对每个位置重新扫描整个窗口（否则退化为 O(N·M)）
+        let mut window_sum: Flt = signal[..template_len].iter().sum();
+        let mut window_sq_sum: Flt = signal[..template_len].iter().map(|x| x * x).sum();
+
+        for m in 0..num_positions {
+            if m > 0 {
+                let leaving = signal[m - 1];
+                let entering = signal[m + template_len - 1];
+                window_sum += entering - leaving;
+                window_sq_sum += entering * entering - leaving * leaving;
+            }
+            let window_var = (window_sq_sum - window_sum * window_sum / template_len as Flt).max(0.0);
+            let window_std = window_var.sqrt();
+
+            // 卷积结果对应位置：反转模板与信号卷积在 index = m + template_len - 1 处。
+            // 由于模板已去均值（Σ t_centered ≈ 0），该卷积值即 Σ_n x[n+m]·t_centered[n]。
+            let conv_idx = m + template_len - 1;
+            let raw = product[conv_idx].re / n as Flt;
+
+            let rho = if window_std > 1e-12 {
+                raw / (window_std * template_std)
+            } else {
+                0.0
+            };
+            correlation.push(rho);
+        }
+
+        let detections: Vec<MatchedFilterDetection> = correlation
+            .iter()
+            .enumerate()
+            .filter(|(_, &rho)| rho > threshold)
+            .map(|(lag, &correlation)| MatchedFilterDetection { lag, correlation })
+            .collect();
+
+        Ok(MatchedFilterResult {
+            correlation,
+            detections,
+        })
+    }
+
     // ============================================
     // 统计分析
     // ============================================
 
     /// 计算统计指标
-    pub fn calculate_statistics(&self, signal: &[f64]) -> Result<StatisticsResult> {
+    pub fn calculate_statistics(&self, signal: &[Flt]) -> Result<StatisticsResult> {
         if signal.is_empty() {
             return Err(SignalError::InsufficientLength {
                 required: 1,
@@ -442,16 +1173,16 @@ impl SignalProcessor {
             });
         }
 
-        let n = signal.len() as f64;
+        let n = signal.len() as Flt;
 
         // 基本统计
-        let mean = signal.iter().sum::<f64>() / n;
-        let variance = signal.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let mean = signal.iter().sum::<Flt>() / n;
+        let variance = signal.iter().map(|x| (x - mean).powi(2)).sum::<Flt>() / n;
         let std_dev = variance.sqrt();
 
         // 极值
-        let min = signal.iter().cloned().fold(f64::INFINITY, f64::min);
-        let max = signal.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let min = signal.iter().cloned().fold(Flt::INFINITY, Flt::min);
+        let max = signal.iter().cloned().fold(Flt::NEG_INFINITY, Flt::max);
         let range = max - min;
 
         // 排序后的数据用于分位数
@@ -473,14 +1204,14 @@ impl SignalProcessor {
         // 高阶矩
         let skewness = signal.iter()
             .map(|x| ((x - mean) / std_dev).powi(3))
-            .sum::<f64>() / n;
+            .sum::<Flt>() / n;
 
         let kurtosis = signal.iter()
             .map(|x| ((x - mean) / std_dev).powi(4))
-            .sum::<f64>() / n - 3.0;
+            .sum::<Flt>() / n - 3.0;
 
         // RMS
-        let rms = (signal.iter().map(|x| x.powi(2)).sum::<f64>() / n).sqrt();
+        let rms = (signal.iter().map(|x| x.powi(2)).sum::<Flt>() / n).sqrt();
 
         // 峰峰值
         let peak_to_peak = max - min;
@@ -513,7 +1244,7 @@ impl SignalProcessor {
     // ============================================
 
     /// Z-Score 异常检测
-    pub fn detect_anomaly_zscore(&self, value: f64, history: &[f64], threshold: f64) -> AnomalyResult {
+    pub fn detect_anomaly_zscore(&self, value: Flt, history: &[Flt], threshold: Flt) -> AnomalyResult {
         let stats = self.calculate_statistics(history).unwrap_or_else(|_| StatisticsResult {
             count: 0,
             mean: 0.0,
@@ -549,7 +1280,7 @@ impl SignalProcessor {
     }
 
     /// IQR 异常检测
-    pub fn detect_anomaly_iqr(&self, value: f64, history: &[f64], k: f64) -> AnomalyResult {
+    pub fn detect_anomaly_iqr(&self, value: Flt, history: &[Flt], k: Flt) -> AnomalyResult {
         let stats = self.calculate_statistics(history).unwrap_or_else(|_| StatisticsResult {
             count: 0,
             mean: 0.0,
@@ -593,7 +1324,7 @@ impl SignalProcessor {
     }
 
     /// MAD (Median Absolute Deviation) 异常检测
-    pub fn detect_anomaly_mad(&self, value: f64, history: &[f64], threshold: f64) -> AnomalyResult {
+    pub fn detect_anomaly_mad(&self, value: Flt, history: &[Flt], threshold: Flt) -> AnomalyResult {
         let stats = self.calculate_statistics(history).unwrap_or_else(|_| StatisticsResult {
             count: 0,
             mean: 0.0,
@@ -614,7 +1345,7 @@ impl SignalProcessor {
         });
 
         // 计算 MAD
-        let mut abs_deviations: Vec<f64> = history
+        let mut abs_deviations: Vec<Flt> = history
             .iter()
             .map(|x| (x - stats.median).abs())
             .collect();
@@ -649,7 +1380,7 @@ impl SignalProcessor {
     // ============================================
 
     /// 提取时域和频域特征
-    pub fn extract_features(&self, signal: &[f64]) -> Result<FeatureSet> {
+    pub fn extract_features(&self, signal: &[Flt]) -> Result<FeatureSet> {
         let stats = self.calculate_statistics(signal)?;
         let fft = self.fft_analysis(signal)?;
 
@@ -657,12 +1388,13 @@ impl SignalProcessor {
         let zero_crossings = signal.windows(2)
             .filter(|w| (w[0] >= 0.0 && w[1] < 0.0) || (w[0] < 0.0 && w[1] >= 0.0))
             .count();
+        let zero_crossing_rate = zero_crossings as Flt * self.sample_rate / signal.len() as Flt;
 
-        let abs_mean = signal.iter().map(|x| x.abs()).sum::<f64>() / signal.len() as f64;
+        let abs_mean = signal.iter().map(|x| x.abs()).sum::<Flt>() / signal.len() as Flt;
         let shape_factor = if abs_mean > 0.0 { stats.rms / abs_mean } else { 0.0 };
         let impulse_factor = if abs_mean > 0.0 { stats.max.abs().max(stats.min.abs()) / abs_mean } else { 0.0 };
         
-        let sqrt_mean = (signal.iter().map(|x| x.abs().sqrt()).sum::<f64>() / signal.len() as f64).powi(2);
+        let sqrt_mean = (signal.iter().map(|x| x.abs().sqrt()).sum::<Flt>() / signal.len() as Flt).powi(2);
         let clearance_factor = if sqrt_mean > 0.0 { stats.max.abs().max(stats.min.abs()) / sqrt_mean } else { 0.0 };
 
         let time_domain = TimeDomainFeatures {
@@ -676,22 +1408,23 @@ impl SignalProcessor {
             impulse_factor,
             clearance_factor,
             zero_crossings,
+            zero_crossing_rate,
         };
 
         // 频域特征
         let spectral_centroid = fft.frequencies.iter()
             .zip(fft.magnitudes.iter())
             .map(|(f, m)| f * m)
-            .sum::<f64>() / fft.magnitudes.iter().sum::<f64>().max(1e-10);
+            .sum::<Flt>() / fft.magnitudes.iter().sum::<Flt>().max(1e-10);
 
         let spectral_bandwidth = (fft.frequencies.iter()
             .zip(fft.magnitudes.iter())
             .map(|(f, m)| (f - spectral_centroid).powi(2) * m)
-            .sum::<f64>() / fft.magnitudes.iter().sum::<f64>().max(1e-10))
+            .sum::<Flt>() / fft.magnitudes.iter().sum::<Flt>().max(1e-10))
             .sqrt();
 
         // 频谱滚降点（95% 能量）
-        let total_energy: f64 = fft.power_spectrum.iter().sum();
+        let total_energy: Flt = fft.power_spectrum.iter().sum();
         let mut cumulative = 0.0;
         let mut spectral_rolloff = 0.0;
         for (i, &power) in fft.power_spectrum.iter().enumerate() {
@@ -705,21 +1438,21 @@ impl SignalProcessor {
         // 频谱平坦度
         let geometric_mean = fft.magnitudes.iter()
             .map(|m| m.max(1e-10).ln())
-            .sum::<f64>()
+            .sum::<Flt>()
             .exp()
-            .powf(1.0 / fft.magnitudes.len() as f64);
-        let arithmetic_mean = fft.magnitudes.iter().sum::<f64>() / fft.magnitudes.len() as f64;
+            .powf(1.0 / fft.magnitudes.len() as Flt);
+        let arithmetic_mean = fft.magnitudes.iter().sum::<Flt>() / fft.magnitudes.len() as Flt;
         let spectral_flatness = if arithmetic_mean > 0.0 { geometric_mean / arithmetic_mean } else { 0.0 };
 
         // 频谱熵
-        let total_mag: f64 = fft.magnitudes.iter().sum();
+        let total_mag: Flt = fft.magnitudes.iter().sum();
         let spectral_entropy = if total_mag > 0.0 {
             -fft.magnitudes.iter()
                 .map(|m| {
                     let p = m / total_mag;
                     if p > 0.0 { p * p.ln() } else { 0.0 }
                 })
-                .sum::<f64>()
+                .sum::<Flt>()
         } else {
             0.0
         };
@@ -727,7 +1460,7 @@ impl SignalProcessor {
         // 频带能量（分成 8 个频带）
         let num_bands = 8;
         let band_size = fft.power_spectrum.len() / num_bands;
-        let band_powers: Vec<f64> = (0..num_bands)
+        let band_powers: Vec<Flt> = (0..num_bands)
             .map(|i| {
                 let start = i * band_size;
                 let end = ((i + 1) * band_size).min(fft.power_spectrum.len());
@@ -751,24 +1484,78 @@ impl SignalProcessor {
         })
     }
 
+    /// 提取 MFCC（梅尔频率倒谱系数）特征
+    pub fn extract_mfcc(&self, signal: &[Flt], num_filters: usize, num_cepstra: usize) -> Result<MfccResult> {
+        if num_cepstra > num_filters {
+            return Err(SignalError::InvalidFilterParams(format!(
+                "倒谱系数数量 {} 不能超过滤波器数量 {}",
+                num_cepstra, num_filters
+            )));
+        }
+
+        let fft = self.fft_analysis(signal)?;
+        let nyquist = self.sample_rate / 2.0;
+
+        // 在梅尔刻度上均匀分布 num_filters+2 个边界点
+        let mel_low = hz_to_mel(0.0);
+        let mel_high = hz_to_mel(nyquist);
+        let mel_points: Vec<Flt> = (0..num_filters + 2)
+            .map(|i| mel_low + (mel_high - mel_low) * i as Flt / (num_filters + 1) as Flt)
+            .collect();
+        let hz_points: Vec<Flt> = mel_points.iter().map(|&m| mel_to_hz(m)).collect();
+
+        let mut mel_log_energies = Vec::with_capacity(num_filters);
+        for i in 0..num_filters {
+            let (f_low, f_center, f_high) = (hz_points[i], hz_points[i + 1], hz_points[i + 2]);
+            let mut energy = 0.0;
+            for (freq, power) in fft.frequencies.iter().zip(fft.power_spectrum.iter()) {
+                let weight = if *freq >= f_low && *freq <= f_center && f_center > f_low {
+                    (freq - f_low) / (f_center - f_low)
+                } else if *freq > f_center && *freq <= f_high && f_high > f_center {
+                    (f_high - freq) / (f_high - f_center)
+                } else {
+                    0.0
+                };
+                energy += weight * power;
+            }
+            mel_log_energies.push(energy.max(1e-10).ln());
+        }
+
+        // DCT-II：c[k] = Σ_i log_energy[i]·cos(π·k·(2i+1)/(2·num_filters))
+        let mfcc: Vec<Flt> = (0..num_cepstra)
+            .map(|k| {
+                mel_log_energies
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &e)| e * (PI * k as Flt * (2.0 * i as Flt + 1.0) / (2.0 * num_filters as Flt)).cos())
+                    .sum()
+            })
+            .collect();
+
+        Ok(MfccResult {
+            mel_log_energies,
+            mfcc,
+        })
+    }
+
     // ============================================
     // 窗函数
     // ============================================
 
     /// 应用窗函数
-    pub fn apply_window(&self, signal: &[f64], window_type: WindowType) -> Vec<f64> {
+    pub fn apply_window(&self, signal: &[Flt], window_type: WindowType) -> Vec<Flt> {
         let n = signal.len();
         let window = match window_type {
             WindowType::Rectangular => vec![1.0; n],
             WindowType::Hanning => (0..n)
-                .map(|i| 0.5 * (1.0 - (2.0 * PI * i as f64 / (n - 1) as f64).cos()))
+                .map(|i| 0.5 * (1.0 - (2.0 * PI * i as Flt / (n - 1) as Flt).cos()))
                 .collect(),
             WindowType::Hamming => (0..n)
-                .map(|i| 0.54 - 0.46 * (2.0 * PI * i as f64 / (n - 1) as f64).cos())
+                .map(|i| 0.54 - 0.46 * (2.0 * PI * i as Flt / (n - 1) as Flt).cos())
                 .collect(),
             WindowType::Blackman => (0..n)
                 .map(|i| {
-                    let x = 2.0 * PI * i as f64 / (n - 1) as f64;
+                    let x = 2.0 * PI * i as Flt / (n - 1) as Flt;
                     0.42 - 0.5 * x.cos() + 0.08 * (2.0 * x).cos()
                 })
                 .collect(),
@@ -776,7 +1563,7 @@ impl SignalProcessor {
                 // 简化的 Kaiser 窗实现
                 (0..n)
                     .map(|i| {
-                        let x = 2.0 * i as f64 / (n - 1) as f64 - 1.0;
+                        let x = 2.0 * i as Flt / (n - 1) as Flt - 1.0;
                         let arg = beta * (1.0 - x * x).sqrt();
                         bessel_i0(arg) / bessel_i0(beta)
                     })
@@ -784,7 +1571,7 @@ impl SignalProcessor {
             }
             WindowType::Gaussian { sigma } => (0..n)
                 .map(|i| {
-                    let x = (i as f64 - (n - 1) as f64 / 2.0) / (sigma * (n - 1) as f64 / 2.0);
+                    let x = (i as Flt - (n - 1) as Flt / 2.0) / (sigma * (n - 1) as Flt / 2.0);
                     (-0.5 * x * x).exp()
                 })
                 .collect(),
@@ -795,7 +1582,7 @@ impl SignalProcessor {
 }
 
 /// 修正的贝塞尔函数 I0（简化实现）
-fn bessel_i0(x: f64) -> f64 {
+fn bessel_i0(x: Flt) -> Flt {
     let ax = x.abs();
     if ax < 3.75 {
         let y = (x / 3.75).powi(2);
@@ -810,6 +1597,119 @@ fn bessel_i0(x: f64) -> f64 {
     }
 }
 
+/// 频率（Hz）转梅尔刻度
+fn hz_to_mel(freq: Flt) -> Flt {
+    2595.0 * (1.0 + freq / 700.0).log10()
+}
+
+/// 梅尔刻度转频率（Hz）
+fn mel_to_hz(mel: Flt) -> Flt {
+    700.0 * ((10.0 as Flt).powf(mel / 2595.0) - 1.0)
+}
+
+// ============================================
+// 确定性测试信号发生器
+// ============================================
+
+/// SplitMix64 确定性伪随机数生成器，仅用于生成可复现的合成测试信号
+struct DeterministicRng {
+    state: u64,
+}
+
+impl DeterministicRng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 区间内的均匀分布
+    fn next_uniform(&mut self) -> Flt {
+        ((self.next_u64() >> 11) as Flt) / ((1u64 << 53) as Flt)
+    }
+
+    /// 通过 Box-Muller 变换生成标准正态分布样本
+    fn next_gaussian(&mut self) -> Flt {
+        let u1 = self.next_uniform().max(1e-12);
+        let u2 = self.next_uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+}
+
+/// 确定性测试信号发生器，用于合成已知真值的波形，配合
+/// `ParallelSignalProcessor::process_batch` 做滤波器基准测试，
+/// 或验证 `fft_analysis`/`fundamental_frequency` 的估计精度
+pub struct SignalGenerator {
+    sample_rate: Flt,
+}
+
+impl SignalGenerator {
+    pub fn new(sample_rate: Flt) -> Self {
+        Self { sample_rate }
+    }
+
+    /// 生成纯正弦波
+    pub fn sine(&self, frequency: Flt, amplitude: Flt, phase: Flt, num_samples: usize) -> Vec<Flt> {
+        (0..num_samples)
+            .map(|i| {
+                let t = i as Flt / self.sample_rate;
+                amplitude * (2.0 * PI * frequency * t + phase).sin()
+            })
+            .collect()
+    }
+
+    /// 生成线性扫频（chirp）信号：`sin(2π·(f0·t + 0.5·k·t²))`，频率从 `f0` 线性扫描到 `f1`
+    pub fn linear_chirp(&self, f0: Flt, f1: Flt, num_samples: usize) -> Vec<Flt> {
+        let duration = num_samples as Flt / self.sample_rate;
+        let k = (f1 - f0) / duration;
+        (0..num_samples)
+            .map(|i| {
+                let t = i as Flt / self.sample_rate;
+                (2.0 * PI * (f0 * t + 0.5 * k * t * t)).sin()
+            })
+            .collect()
+    }
+
+    /// 生成对数扫频（logarithmic chirp）信号，频率从 `f0` 按指数规律扫描到 `f1`
+    pub fn logarithmic_chirp(&self, f0: Flt, f1: Flt, num_samples: usize) -> Vec<Flt> {
+        let duration = num_samples as Flt / self.sample_rate;
+        let k = (f1 / f0).ln();
+        (0..num_samples)
+            .map(|i| {
+                let t = i as Flt / self.sample_rate;
+                let phase = 2.0 * PI * f0 * duration * ((k * t / duration).exp() - 1.0) / k;
+                phase.sin()
+            })
+            .collect()
+    }
+
+    /// 生成指定 RMS 的白高斯噪声（`seed` 固定则结果可复现）
+    pub fn white_gaussian_noise(&self, rms: Flt, num_samples: usize, seed: u64) -> Vec<Flt> {
+        let mut rng = DeterministicRng::new(seed);
+        (0..num_samples).map(|_| rms * rng.next_gaussian()).collect()
+    }
+
+    /// 生成限带噪声：先生成白高斯噪声，再用 Butterworth 带通滤波器约束其频带
+    pub fn band_limited_noise(
+        &self,
+        low: Flt,
+        high: Flt,
+        rms: Flt,
+        num_samples: usize,
+        seed: u64,
+    ) -> Result<Vec<Flt>> {
+        let white = self.white_gaussian_noise(rms, num_samples, seed);
+        let processor = SignalProcessor::new(self.sample_rate)?;
+        processor.apply_filter(&white, FilterType::BandPass { low, high })
+    }
+}
+
 // ============================================
 // 并行处理
 // ============================================
@@ -820,14 +1720,14 @@ pub struct ParallelSignalProcessor {
 }
 
 impl ParallelSignalProcessor {
-    pub fn new(sample_rate: f64) -> Result<Self> {
+    pub fn new(sample_rate: Flt) -> Result<Self> {
         Ok(Self {
             processor: SignalProcessor::new(sample_rate)?,
         })
     }
 
     /// 并行处理多个信号
-    pub fn process_batch(&self, signals: &[Vec<f64>], filter_type: FilterType) -> Vec<Result<Vec<f64>>> {
+    pub fn process_batch(&self, signals: &[Vec<Flt>], filter_type: FilterType) -> Vec<Result<Vec<Flt>>> {
         use rayon::prelude::*;
         
         signals
@@ -837,7 +1737,7 @@ impl ParallelSignalProcessor {
     }
 
     /// 并行提取特征
-    pub fn extract_features_batch(&self, signals: &[Vec<f64>]) -> Vec<Result<FeatureSet>> {
+    pub fn extract_features_batch(&self, signals: &[Vec<Flt>]) -> Vec<Result<FeatureSet>> {
         use rayon::prelude::*;
         
         signals
@@ -849,9 +1749,9 @@ impl ParallelSignalProcessor {
     /// 并行异常检测
     pub fn detect_anomalies_batch(
         &self,
-        values: &[f64],
-        history: &[f64],
-        threshold: f64,
+        values: &[Flt],
+        history: &[Flt],
+        threshold: Flt,
     ) -> Vec<AnomalyResult> {
         use rayon::prelude::*;
         
@@ -862,6 +1762,281 @@ impl ParallelSignalProcessor {
     }
 }
 
+// ============================================
+// 流式（在线）处理
+// ============================================
+
+/// 持久化的流式滤波器状态，在多次 `push` 调用间保留其延迟寄存器/运行和。
+///
+/// 对 `ExponentialMovingAverage`，逐样本输出与对等长度信号批量滤波的结果一致；
+/// 对 `MovingAverage`，从第 `window_size` 个样本起一致，此前流式输出为已到达样本的
+/// 均值，而批量输出为前 `window_size` 个样本的均值。`Biquad` 变体是单次前向因果滤波（对应 `BiquadCascade::process`），
+/// 而 `apply_filter` 对二阶节走的是 `filtfilt`（前向+反向零相位滤波），二者结果
+/// 不同——流式场景下无法获得零相位的批量结果，调用方不应假设一致。
+enum StreamingFilter {
+    MovingAverage {
+        window_size: usize,
+        sum: Flt,
+        buffer: VecDeque<Flt>,
+    },
+    ExponentialMovingAverage {
+        alpha: Flt,
+        state: Option<Flt>,
+    },
+    Biquad(BiquadCascade),
+}
+
+impl StreamingFilter {
+    fn push(&mut self, sample: Flt) -> Flt {
+        match self {
+            StreamingFilter::MovingAverage {
+                window_size,
+                sum,
+                buffer,
+            } => {
+                buffer.push_back(sample);
+                *sum += sample;
+                if buffer.len() > *window_size {
+                    *sum -= buffer.pop_front().unwrap();
+                }
+                *sum / buffer.len() as Flt
+            }
+            StreamingFilter::ExponentialMovingAverage { alpha, state } => {
+                let ema = match state {
+                    Some(prev) => *alpha * sample + (1.0 - *alpha) * *prev,
+                    None => sample,
+                };
+                *state = Some(ema);
+                ema
+            }
+            StreamingFilter::Biquad(cascade) => cascade.process_sample(sample),
+        }
+    }
+}
+
+fn streaming_filter_from_type(filter_type: &FilterType, sample_rate: Flt) -> Result<StreamingFilter> {
+    match filter_type {
+        FilterType::MovingAverage { window_size } => Ok(StreamingFilter::MovingAverage {
+            window_size: *window_size,
+            sum: 0.0,
+            buffer: VecDeque::with_capacity(*window_size),
+        }),
+        FilterType::ExponentialMovingAverage { alpha } => Ok(StreamingFilter::ExponentialMovingAverage {
+            alpha: *alpha,
+            state: None,
+        }),
+        FilterType::LowPass { cutoff } => Ok(StreamingFilter::Biquad(BiquadCascade::new(
+            butterworth_lowpass_sections(4, *cutoff, sample_rate)?,
+        ))),
+        FilterType::HighPass { cutoff } => {
+            let lowpass_sections = butterworth_lowpass_sections(4, *cutoff, sample_rate)?;
+            let omega_c = prewarped_omega_c(*cutoff, sample_rate);
+            Ok(StreamingFilter::Biquad(BiquadCascade::new(
+                lowpass_sections_to_highpass(&lowpass_sections, omega_c, sample_rate),
+            )))
+        }
+        FilterType::BandPass { .. } | FilterType::BandStop { .. } => Err(
+            SignalError::InvalidFilterParams("流式处理暂不支持带通/带阻滤波器".to_string()),
+        ),
+        FilterType::Median { .. } => Err(SignalError::InvalidFilterParams(
+            "流式处理暂不支持中值滤波器（无有界状态表示）".to_string(),
+        )),
+        FilterType::BandPassQ { center, q } => Ok(StreamingFilter::Biquad(BiquadCascade::new(
+            vec![design_bandpass_q_biquad(*center, *q, sample_rate)?],
+        ))),
+        FilterType::Notch { center, q } => Ok(StreamingFilter::Biquad(BiquadCascade::new(vec![
+            design_notch_biquad(*center, *q, sample_rate)?,
+        ]))),
+        FilterType::ButterworthLowPass { cutoff, order } => Ok(StreamingFilter::Biquad(
+            BiquadCascade::new(butterworth_lowpass_sections(*order, *cutoff, sample_rate)?),
+        )),
+    }
+}
+
+/// 流式信号处理器：持有固定容量的环形缓冲区与持久化滤波器状态，
+/// 逐样本处理时无需重新缓冲或重新计算整个历史。
+///
+/// `ExponentialMovingAverage` 滤波器的结果与对等长度信号的批量滤波结果一致，
+/// `MovingAverage` 从第 `window_size` 个样本起一致；二阶节（`LowPass`/`HighPass`/`BandPassQ`/`Notch`/`ButterworthLowPass`
+/// 等）是单次前向因果滤波，与 `apply_filter` 的零相位 `filtfilt` 批量结果不同。
+pub struct StreamingProcessor {
+    sample_rate: Flt,
+    capacity: usize,
+    buffer: VecDeque<Flt>,
+    filter: StreamingFilter,
+}
+
+impl StreamingProcessor {
+    /// 创建新的流式处理器，`capacity` 为环形缓冲区保留的历史样本数
+    pub fn new(sample_rate: Flt, capacity: usize, filter_type: FilterType) -> Result<Self> {
+        if sample_rate <= 0.0 {
+            return Err(SignalError::InvalidSampleRate(sample_rate));
+        }
+
+        Ok(Self {
+            sample_rate,
+            capacity,
+            buffer: VecDeque::with_capacity(capacity),
+            filter: streaming_filter_from_type(&filter_type, sample_rate)?,
+        })
+    }
+
+    /// 推入一个新样本，返回经过持久化滤波器状态处理后的输出
+    pub fn push(&mut self, sample: Flt) -> Flt {
+        self.buffer.push_back(sample);
+        if self.buffer.len() > self.capacity {
+            self.buffer.pop_front();
+        }
+        self.filter.push(sample)
+    }
+
+    /// 当前环形缓冲区中保留的历史样本
+    pub fn buffer(&self) -> &VecDeque<Flt> {
+        &self.buffer
+    }
+
+    pub fn sample_rate(&self) -> Flt {
+        self.sample_rate
+    }
+}
+
+/// 在线异常检测器：维护固定容量的滑动窗口，使用 Welford 算法增量更新均值/方差
+/// （而非每次调用都对整段历史重新调用 `calculate_statistics`），
+/// 并基于窗口内样本提供滚动分位数估计（用于 MAD 检测）
+pub struct OnlineDetector {
+    capacity: usize,
+    window: VecDeque<Flt>,
+    count: usize,
+    mean: Flt,
+    m2: Flt,
+    threshold: Flt,
+}
+
+impl OnlineDetector {
+    /// 创建新的在线检测器，`capacity` 为滚动窗口的样本数，Z-Score 阈值默认为 3.0
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            window: VecDeque::with_capacity(capacity),
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            threshold: 3.0,
+        }
+    }
+
+    /// 设置 Z-Score 判定阈值
+    pub fn with_threshold(mut self, threshold: Flt) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// 推入新值，使用构造时设定的阈值返回滚动 Z-Score 异常检测结果
+    pub fn push(&mut self, value: Flt) -> AnomalyResult {
+        self.push_zscore(value, self.threshold)
+    }
+
+    /// Welford 在线更新：先加入新样本，若超出窗口容量再对移出的旧样本做对称的减法更新
+    fn update(&mut self, value: Flt) {
+        self.window.push_back(value);
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as Flt;
+        let delta2 = value - self.mean;
+        self.m2 += delta * delta2;
+
+        if self.window.len() > self.capacity {
+            let removed = self.window.pop_front().unwrap();
+            self.count -= 1;
+            if self.count == 0 {
+                self.mean = 0.0;
+                self.m2 = 0.0;
+            } else {
+                let delta = removed - self.mean;
+                self.mean -= delta / self.count as Flt;
+                let delta2 = removed - self.mean;
+                self.m2 -= delta * delta2;
+            }
+        }
+    }
+
+    fn std_dev(&self) -> Flt {
+        if self.count > 1 {
+            (self.m2 / (self.count - 1) as Flt).sqrt()
+        } else {
+            0.0
+        }
+    }
+
+    /// 滚动窗口中位数（用于 MAD 估计）
+    fn rolling_median(&self) -> Flt {
+        let mut sorted: Vec<Flt> = self.window.iter().cloned().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        if sorted.is_empty() {
+            return 0.0;
+        }
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        }
+    }
+
+    /// 推入新值并基于当前滚动统计量返回 Z-Score 异常检测结果
+    pub fn push_zscore(&mut self, value: Flt, threshold: Flt) -> AnomalyResult {
+        self.update(value);
+        let std_dev = self.std_dev();
+        let z_score = if std_dev > 0.0 {
+            (value - self.mean).abs() / std_dev
+        } else {
+            0.0
+        };
+
+        AnomalyResult {
+            is_anomaly: z_score > threshold,
+            score: z_score,
+            threshold,
+            method: "Z-Score(Streaming)".to_string(),
+            details: Some(format!("mean={:.4}, std={:.4}", self.mean, std_dev)),
+        }
+    }
+
+    /// 推入新值并基于滚动窗口中位数的 MAD 返回异常检测结果
+    pub fn push_mad(&mut self, value: Flt, threshold: Flt) -> AnomalyResult {
+        self.update(value);
+        let median = self.rolling_median();
+
+        let mut abs_deviations: Vec<Flt> = self.window.iter().map(|x| (x - median).abs()).collect();
+        abs_deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mad = if abs_deviations.is_empty() {
+            0.0
+        } else {
+            let mid = abs_deviations.len() / 2;
+            if abs_deviations.len() % 2 == 0 {
+                (abs_deviations[mid - 1] + abs_deviations[mid]) / 2.0
+            } else {
+                abs_deviations[mid]
+            }
+        };
+        let mad_corrected = mad * 1.4826;
+
+        let score = if mad_corrected > 0.0 {
+            (value - median).abs() / mad_corrected
+        } else {
+            0.0
+        };
+
+        AnomalyResult {
+            is_anomaly: score > threshold,
+            score,
+            threshold,
+            method: "MAD(Streaming)".to_string(),
+            details: Some(format!("median={:.4}, mad={:.4}", median, mad)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -899,8 +2074,8 @@ mod tests {
     #[test]
     fn test_fft() {
         let processor = SignalProcessor::new(1000.0).unwrap();
-        let signal: Vec<f64> = (0..1024)
-            .map(|i| (2.0 * PI * 50.0 * i as f64 / 1000.0).sin())
+        let signal: Vec<Flt> = (0..1024)
+            .map(|i| (2.0 * PI * 50.0 * i as Flt / 1000.0).sin())
             .collect();
         let result = processor.fft_analysis(&signal);
         assert!(result.is_ok());
@@ -922,6 +2097,283 @@ mod tests {
         assert!(anomaly.is_anomaly);
     }
 
+    #[test]
+    fn test_online_detector_push_uses_configured_threshold() {
+        let mut detector = OnlineDetector::new(50).with_threshold(2.0);
+        for v in [1.0, 2.0, 3.0, 4.0, 5.0, 4.0, 3.0, 2.0, 1.0, 2.0] {
+            detector.push(v);
+        }
+
+        assert!(!detector.push(3.0).is_anomaly);
+        assert!(detector.push(100.0).is_anomaly);
+    }
+
+    #[test]
+    fn test_zero_crossing_rate() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let signal: Vec<Flt> = (0..1000)
+            .map(|i| (2.0 * PI * 50.0 * i as Flt / 1000.0).sin())
+            .collect();
+
+        let features = processor.extract_features(&signal).unwrap();
+        // 50Hz 正弦波每秒过零 100 次
+        assert!((features.time_domain.zero_crossing_rate - 100.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_signal_generator_sine_matches_fft() {
+        let generator = SignalGenerator::new(1000.0);
+        let signal = generator.sine(50.0, 1.0, 0.0, 1024);
+
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let fft = processor.fft_analysis(&signal).unwrap();
+        assert!((fft.dominant_frequency - 50.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_signal_generator_linear_chirp_sweeps_full_band() {
+        let generator = SignalGenerator::new(1000.0);
+        let signal = generator.linear_chirp(50.0, 450.0, 1024);
+
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        // 整段信号能量应集中在 [f0, f1] 区间内，而不是被 π 而非 2π 的相位错误
+        // 缩小到一半频段 [f0/2, f1/2]
+        let fft = processor.fft_analysis(&signal).unwrap();
+        assert!(fft.dominant_frequency >= 50.0 && fft.dominant_frequency <= 450.0);
+
+        // 信号末尾窗口的瞬时频率应接近 f1，而非 f1/2
+        let tail = &signal[signal.len() - 128..];
+        let tail_fft = processor.fft_analysis(tail).unwrap();
+        assert!(tail_fft.dominant_frequency > 300.0);
+    }
+
+    #[test]
+    fn test_signal_generator_deterministic_noise() {
+        let generator = SignalGenerator::new(1000.0);
+        let a = generator.white_gaussian_noise(1.0, 256, 42);
+        let b = generator.white_gaussian_noise(1.0, 256, 42);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_power_spectrum_welch_convenience_wrapper() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let signal: Vec<Flt> = (0..4096)
+            .map(|i| (2.0 * PI * 50.0 * i as Flt / 1000.0).sin())
+            .collect();
+
+        let spectrum = processor
+            .power_spectrum_welch(&signal, 256, 0.5, WindowType::Hanning)
+            .unwrap();
+        assert_eq!(spectrum.frequencies.len(), spectrum.power.len());
+    }
+
+    #[test]
+    fn test_notch_filter_attenuates_center_frequency() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let n = 2048;
+        let signal: Vec<Flt> = (0..n)
+            .map(|i| (2.0 * PI * 100.0 * i as Flt / 1000.0).sin())
+            .collect();
+
+        let filtered = processor
+            .apply_filter(&signal, FilterType::Notch { center: 100.0, q: 10.0 })
+            .unwrap();
+
+        let input_power: Flt = processor.fft_analysis(&signal).unwrap().total_power;
+        let output_power: Flt = processor.fft_analysis(&filtered).unwrap().total_power;
+        assert!(output_power < input_power * 0.2);
+    }
+
+    #[test]
+    fn test_autocorrelation_fundamental_frequency() {
+        let processor = SignalProcessor::new(8000.0).unwrap();
+        let signal: Vec<Flt> = (0..4096)
+            .map(|i| (2.0 * PI * 200.0 * i as Flt / 8000.0).sin())
+            .collect();
+
+        let freq = processor
+            .autocorrelation_fundamental_frequency(&signal)
+            .unwrap();
+        assert!(freq.is_some());
+        assert!((freq.unwrap() - 200.0).abs() < 5.0);
+
+        let silence = vec![0.0; 1024];
+        let none_freq = processor
+            .autocorrelation_fundamental_frequency(&silence)
+            .unwrap();
+        assert!(none_freq.is_none());
+    }
+
+    #[test]
+    fn test_streaming_processor_matches_batch_moving_average() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let signal: Vec<Flt> = (0..20).map(|i| i as Flt).collect();
+
+        let batch = processor
+            .apply_filter(&signal, FilterType::MovingAverage { window_size: 3 })
+            .unwrap();
+
+        let mut streaming =
+            StreamingProcessor::new(1000.0, 100, FilterType::MovingAverage { window_size: 3 }).unwrap();
+        let streamed: Vec<Flt> = signal.iter().map(|&x| streaming.push(x)).collect();
+
+        for (b, s) in batch.iter().zip(streamed.iter()) {
+            assert!((b - s).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_online_detector_zscore() {
+        let mut detector = OnlineDetector::new(50);
+        for v in [1.0, 2.0, 3.0, 4.0, 5.0, 4.0, 3.0, 2.0, 1.0, 2.0] {
+            detector.push_zscore(v, 2.0);
+        }
+
+        let normal = detector.push_zscore(3.0, 2.0);
+        assert!(!normal.is_anomaly);
+
+        let anomaly = detector.push_zscore(100.0, 2.0);
+        assert!(anomaly.is_anomaly);
+    }
+
+    #[test]
+    fn test_matched_filter_detects_chirp() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let template = processor.chirp_template(50.0, 200.0, 0.1);
+
+        let mut signal = vec![0.0; 300];
+        signal.extend(vec![0.0; 50]);
+        signal.extend(template.iter());
+        signal.extend(vec![0.0; 50]);
+
+        let result = processor.matched_filter(&signal, &template, 0.5).unwrap();
+        assert!(!result.detections.is_empty());
+        let best = result
+            .detections
+            .iter()
+            .max_by(|a, b| a.correlation.partial_cmp(&b.correlation).unwrap())
+            .unwrap();
+        assert!((best.lag as i64 - 350).abs() < 5);
+    }
+
+    #[test]
+    fn test_fundamental_frequency() {
+        let processor = SignalProcessor::new(8000.0).unwrap();
+        let signal: Vec<Flt> = (0..4096)
+            .map(|i| (2.0 * PI * 200.0 * i as Flt / 8000.0).sin())
+            .collect();
+
+        let pitch = processor.fundamental_frequency(&signal).unwrap();
+        assert!(pitch.autocorrelation_freq.is_some());
+        let freq = pitch.autocorrelation_freq.unwrap();
+        assert!((freq - 200.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_extract_mfcc() {
+        let processor = SignalProcessor::new(16000.0).unwrap();
+        let signal: Vec<Flt> = (0..1024)
+            .map(|i| (2.0 * PI * 440.0 * i as Flt / 16000.0).sin())
+            .collect();
+
+        let result = processor.extract_mfcc(&signal, 26, 13).unwrap();
+        assert_eq!(result.mel_log_energies.len(), 26);
+        assert_eq!(result.mfcc.len(), 13);
+    }
+
+    #[test]
+    fn test_power_spectral_density_welch() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let signal: Vec<Flt> = (0..4096)
+            .map(|i| (2.0 * PI * 50.0 * i as Flt / 1000.0).sin())
+            .collect();
+
+        let psd = processor
+            .power_spectral_density(&signal, 256, 0.5, WindowType::Hanning)
+            .unwrap();
+
+        let peak_idx = psd
+            .psd
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(i, _)| i)
+            .unwrap();
+        let peak_freq = psd.frequencies[peak_idx];
+        assert!((peak_freq - 50.0).abs() < psd.resolution * 2.0);
+    }
+
+    #[test]
+    fn test_butterworth_lowpass_attenuates_high_frequency() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let n = 2048;
+        let low_freq = 10.0;
+        let high_freq = 300.0;
+        let signal: Vec<Flt> = (0..n)
+            .map(|i| {
+                let t = i as Flt / 1000.0;
+                (2.0 * PI * low_freq * t).sin() + (2.0 * PI * high_freq * t).sin()
+            })
+            .collect();
+
+        let filtered = processor
+            .apply_filter(&signal, FilterType::LowPass { cutoff: 50.0 })
+            .unwrap();
+
+        let input_high_power: Flt = processor.fft_analysis(&signal).unwrap().power_spectrum
+            [((high_freq / (1000.0 / n as Flt)) as usize).saturating_sub(2)
+                ..((high_freq / (1000.0 / n as Flt)) as usize + 2)]
+            .iter()
+            .sum();
+        let output_high_power: Flt = processor.fft_analysis(&filtered).unwrap().power_spectrum
+            [((high_freq / (1000.0 / n as Flt)) as usize).saturating_sub(2)
+                ..((high_freq / (1000.0 / n as Flt)) as usize + 2)]
+            .iter()
+            .sum();
+
+        assert!(output_high_power < input_high_power * 0.1);
+    }
+
+    #[test]
+    fn test_butterworth_highpass_passes_high_frequency_at_unity_gain() {
+        let processor = SignalProcessor::new(1000.0).unwrap();
+        let n = 2048;
+        let pass_freq = 300.0;
+        let signal: Vec<Flt> = (0..n)
+            .map(|i| {
+                let t = i as Flt / 1000.0;
+                (2.0 * PI * pass_freq * t).sin()
+            })
+            .collect();
+
+        let filtered = processor
+            .apply_filter(&signal, FilterType::HighPass { cutoff: 50.0 })
+            .unwrap();
+
+        // passband 增益应接近 1，而不是被 lowpass_sections_to_highpass 的分子误用
+        // 衰减到 (fc / 2fs)^2 这种量级
+        let input_rms = (signal.iter().map(|v| v * v).sum::<Flt>() / n as Flt).sqrt();
+        let output_rms = (filtered.iter().map(|v| v * v).sum::<Flt>() / n as Flt).sqrt();
+        assert!(
+            (output_rms / input_rms - 1.0).abs() < 0.1,
+            "通带增益应接近 1，实际为 {}",
+            output_rms / input_rms
+        );
+    }
+
+    #[test]
+    fn test_biquad_cascade_filtfilt_preserves_length() {
+        let sections = butterworth_lowpass_sections(4, 50.0, 1000.0).unwrap();
+        let cascade = BiquadCascade::new(sections);
+        let signal: Vec<Flt> = (0..512)
+            .map(|i| (2.0 * PI * 20.0 * i as Flt / 1000.0).sin())
+            .collect();
+
+        let filtered = cascade.filtfilt(&signal).unwrap();
+        assert_eq!(filtered.len(), signal.len());
+    }
+
     #[test]
     fn test_window_functions() {
         let processor = SignalProcessor::new(1000.0).unwrap();